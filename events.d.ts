@@ -0,0 +1,21 @@
+/* tslint:disable */
+/* eslint-disable */
+
+import type { PacketReliability } from './index'
+
+/** What `RaknetClient.onEvent` returns, discriminated by `type`. */
+export type ClientEvent =
+  | { type: 'pong'; serverGuid: bigint; motd: string }
+  | { type: 'handshake_progress'; state: 'open_request_2_sent' | 'connection_request_sent'; mtu: number }
+  | { type: 'protocol_changed'; protocol: number }
+  | { type: 'connected'; mtu: number; serverGuid: bigint }
+  | { type: 'connect_failed'; reason: 'timeout'; stage: 'open_request_1_sent' | 'open_request_2_sent' | 'connection_request_sent' }
+  | { type: 'connect_failed'; reason: 'incompatible_protocol'; serverProtocol: number }
+  | { type: 'connect_failed'; reason: 'banned' | 'no_free_incoming_connections' | 'already_connected' | 'connection_attempt_failed' | 'ip_recently_connected' }
+  | { type: 'disconnected'; reason: 'timeout' | 'client_requested' | 'server_requested' }
+  | { type: 'message'; channel: number; reliability: PacketReliability; payload: Buffer }
+  | { type: 'latency_updated'; rtt: number }
+  | { type: 'receipt_acked'; receipt: number }
+  | { type: 'receipt_lost'; receipt: number }
+  | { type: 'split_dropped'; splitId: number; reason: 'too_many_fragments' | 'too_large' | 'too_many_splits' | 'inconsistent' | 'timed_out' }
+  | { type: 'unknown_packet'; id: number; payload: Buffer }
@@ -3,17 +3,59 @@
 
 /* auto-generated by NAPI-RS */
 
-export interface JsEvent {
-  name: string
-  data: Array<number>
+export const enum PacketPriority {
+  Immediate = 0,
+  High = 1,
+  Medium = 2,
+  Low = 3
+}
+export const enum PacketReliability {
+  Unreliable = 0,
+  UnreliableSequenced = 1,
+  Reliable = 2,
+  ReliableOrdered = 3,
+  ReliableSequenced = 4,
+  UnreliableWithAckReceipt = 5,
+  ReliableWithAckReceipt = 6,
+  ReliableOrderedWithAckReceipt = 7
+}
+/** Constructor options; anything left out keeps the `ClientConfig` default. */
+export interface JsClientOptions {
+  guid?: bigint
+  protocol?: number
+  adoptServerProtocol?: boolean
+  mtuSizes?: Array<number>
+  mtuAttemptsPerSize?: number
+  minMtuSize?: number
+  maxMtuSize?: number
+  bindAddress?: string
+  handshakeRetryInterval?: number
+  handshakeMaxRetries?: number
+  disconnectTimeout?: number
+  sessionTimeout?: number
+  pingInterval?: number
+  maxSplitFragments?: number
+  maxSplitBytes?: number
+  maxConcurrentSplits?: number
+  splitTimeout?: number
+  magic?: Array<number>
+}
+export interface JsLatency {
+  rtt: number
+  rttVariance: number
+  lastPing: number
 }
 export declare class RaknetClient {
-  constructor(host: string, port: number)
+  constructor(host: string, port: number, options?: JsClientOptions | undefined | null)
   connect(): void
   receive(): Array<number>
-  frameAndSend(data: Buffer): void
+  frameAndSend(data: Buffer, priority?: PacketPriority | undefined | null): void
+  send(data: Buffer, reliability: PacketReliability, channel: number, priority: PacketPriority): number
   tick(): void
   ping(): void
+  disconnect(): void
+  latency(): JsLatency
   isConnected(): boolean
-  onEvent(): JsEvent | null
+  /** Returns the next event, a plain object discriminated by `type`. */
+  onEvent(): import('./events').ClientEvent | null
 }
@@ -1,4 +1,4 @@
-use super::Endianness;
+use super::{BinaryError, Endianness};
 
 pub struct BinaryStream {
     pub binary: Vec<u8>,
@@ -7,21 +7,27 @@ pub struct BinaryStream {
 
 impl BinaryStream {
     pub fn new(binary: Option<Vec<u8>>, offset: Option<usize>) -> Self {
-        Self { binary: binary.unwrap_or(Vec::new()), offset: offset.unwrap_or(0) }
+        Self { binary: binary.unwrap_or_default(), offset: offset.unwrap_or(0) }
     }
 
-    pub fn read(&mut self, size: usize) -> Vec<u8> {
-        if size > self.binary.len() {
-            panic!("Size out of bounds");
-        }
+    pub fn read(&mut self, size: usize) -> Result<Vec<u8>, BinaryError> {
+        self.read_field(size, "bytes")
+    }
 
-        if self.offset + size > self.binary.len() {
-            panic!("\nOffset out of bounds\n");
+    fn read_field(&mut self, size: usize, field: &'static str) -> Result<Vec<u8>, BinaryError> {
+        let available = self.binary.len().saturating_sub(self.offset);
+        if size > available {
+            return Err(BinaryError::OutOfBounds { field, wanted: size, available });
         }
 
         let data = self.binary[self.offset..self.offset + size].to_vec();
         self.offset += size;
-        data
+        Ok(data)
+    }
+
+    fn read_array<const N: usize>(&mut self, field: &'static str) -> Result<[u8; N], BinaryError> {
+        let data = self.read_field(N, field)?;
+        Ok(data.try_into().unwrap())
     }
 
     pub fn write(&mut self, data: Vec<u8>) {
@@ -49,7 +55,7 @@ impl BinaryStream {
     }
 
     pub fn read_remaining(&mut self) -> Vec<u8> {
-        self.binary[self.offset..self.binary.len()].to_vec()
+        self.binary.get(self.offset..).unwrap_or_default().to_vec()
     }
 
     pub fn cursor_at_start(&mut self) -> bool {
@@ -57,40 +63,39 @@ impl BinaryStream {
     }
 
     pub fn cursor_at_end(&mut self) -> bool {
-        self.offset == self.binary.len()
+        self.offset >= self.binary.len()
     }
 
-    pub fn read_bytes(&mut self, size: usize) -> Vec<u8> {
-        let data = self.read(size);
-        data
+    pub fn read_bytes(&mut self, size: usize) -> Result<Vec<u8>, BinaryError> {
+        self.read(size)
     }
 
     pub fn write_bytes(&mut self, data: Vec<u8>) {
         self.write(data);
     }
 
-    pub fn read_byte(&mut self) -> u8 {
-        self.read(1)[0]
+    pub fn read_byte(&mut self) -> Result<u8, BinaryError> {
+        Ok(self.read_array::<1>("byte")?[0])
     }
 
     pub fn write_byte(&mut self, data: u8) {
         self.write(vec![data]);
     }
 
-    pub fn read_u8(&mut self) -> u8 {
-        self.read(1)[0]
+    pub fn read_u8(&mut self) -> Result<u8, BinaryError> {
+        Ok(self.read_array::<1>("u8")?[0])
     }
 
     pub fn write_u8(&mut self, data: u8) {
         self.write(vec![data]);
     }
 
-   pub fn read_u16(&mut self, endianness: Option<Endianness>) -> u16 {
-        let bytes = self.read(2);
-        match endianness.unwrap_or(Endianness::Big) {
-            Endianness::Big => u16::from_be_bytes(bytes.try_into().unwrap()),
-            Endianness::Little => u16::from_le_bytes(bytes.try_into().unwrap()),
-        }
+    pub fn read_u16(&mut self, endianness: Option<Endianness>) -> Result<u16, BinaryError> {
+        let bytes = self.read_array("u16")?;
+        Ok(match endianness.unwrap_or(Endianness::Big) {
+            Endianness::Big => u16::from_be_bytes(bytes),
+            Endianness::Little => u16::from_le_bytes(bytes),
+        })
     }
 
     pub fn write_u16(&mut self, data: u16, endianness: Option<Endianness>) {
@@ -101,14 +106,14 @@ impl BinaryStream {
         self.write(bytes.to_vec());
     }
 
-    pub fn read_u24(&mut self, endianness: Option<Endianness>) -> u32 {
+    pub fn read_u24(&mut self, endianness: Option<Endianness>) -> Result<u32, BinaryError> {
         let endian = endianness.unwrap_or(Endianness::Big);
-        let bytes = self.read(3);
+        let bytes: [u8; 3] = self.read_array("u24")?;
         
-        match endian {
+        Ok(match endian {
             Endianness::Big => u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]),
             Endianness::Little => u32::from_le_bytes([bytes[0], bytes[1], bytes[2], 0]),
-        }
+        })
     }
 
     pub fn write_u24(&mut self, data: u32, endianness: Option<Endianness>) {
@@ -118,12 +123,12 @@ impl BinaryStream {
         }
     }
 
-    pub fn read_u32(&mut self, endianness: Option<Endianness>) -> u32 {
-        let data = self.read(4);
-        match endianness.unwrap_or(Endianness::Big) {
-            Endianness::Big => u32::from_be_bytes([data[0], data[1], data[2], data[3]]),
-            Endianness::Little => u32::from_le_bytes([data[0], data[1], data[2], data[3]]),
-        }
+    pub fn read_u32(&mut self, endianness: Option<Endianness>) -> Result<u32, BinaryError> {
+        let data = self.read_array("u32")?;
+        Ok(match endianness.unwrap_or(Endianness::Big) {
+            Endianness::Big => u32::from_be_bytes(data),
+            Endianness::Little => u32::from_le_bytes(data),
+        })
     }
 
     pub fn write_u32(&mut self, data: u32, endianness: Option<Endianness>) {
@@ -135,13 +140,13 @@ impl BinaryStream {
         self.write(bytes.to_vec());
     }
 
-    pub fn read_u64(&mut self, endianness: Option<Endianness>) -> u64 {
-        let data = self.read(8);
-        match endianness {
-            Some(Endianness::Big) => u64::from_be_bytes(data.try_into().unwrap()),
-            Some(Endianness::Little) => u64::from_le_bytes(data.try_into().unwrap()),
-            None => u64::from_be_bytes(data.try_into().unwrap()),
-        }
+    pub fn read_u64(&mut self, endianness: Option<Endianness>) -> Result<u64, BinaryError> {
+        let data = self.read_array("u64")?;
+        Ok(match endianness {
+            Some(Endianness::Big) => u64::from_be_bytes(data),
+            Some(Endianness::Little) => u64::from_le_bytes(data),
+            None => u64::from_be_bytes(data),
+        })
     }
 
     pub fn write_u64(&mut self, data: u64, endianness: Option<Endianness>) {
@@ -153,45 +158,45 @@ impl BinaryStream {
         self.write(bytes.to_vec());
     }
 
-    pub fn read_bool(&mut self) -> bool {
-        self.read_u8() != 0
+    pub fn read_bool(&mut self) -> Result<bool, BinaryError> {
+        Ok(self.read_array::<1>("bool")?[0] != 0)
     }
 
     pub fn write_bool(&mut self, data: bool) {
         self.write_u8(if data { 1 } else { 0 });
     }
 
-    pub fn read_i8(&mut self) -> i8 {
-        self.read_u8() as i8
+    pub fn read_i8(&mut self) -> Result<i8, BinaryError> {
+        Ok(self.read_array::<1>("i8")?[0] as i8)
     }
 
     pub fn write_i8(&mut self, data: i8) {
         self.write_u8(data as u8);
     }
 
-    pub fn read_i16(&mut self, endianness: Option<Endianness>) -> i16 {
-        self.read_u16(endianness) as i16
+    pub fn read_i16(&mut self, endianness: Option<Endianness>) -> Result<i16, BinaryError> {
+        Ok(self.read_u16(endianness).map_err(|e| e.with_field("i16"))? as i16)
     }
 
     pub fn write_i16(&mut self, data: i16, endianness: Option<Endianness>) {
         self.write_u16(data as u16, endianness);
     }
 
-    pub fn read_i24(&mut self, endianness: Option<Endianness>) -> i32 {
-        self.read_u24(endianness) as i32
+    pub fn read_i24(&mut self, endianness: Option<Endianness>) -> Result<i32, BinaryError> {
+        Ok(self.read_u24(endianness).map_err(|e| e.with_field("i24"))? as i32)
     }
 
     pub fn write_i24(&mut self, data: i32, endianness: Option<Endianness>) {
         self.write_u24(data as u32, endianness);
     }
 
-    pub fn read_i32(&mut self, endianness: Option<Endianness>) -> i32 {
-        let data = self.read(4);
-        match endianness {
-            Some(Endianness::Big) => i32::from_be_bytes(data.try_into().unwrap()),
-            Some(Endianness::Little) => i32::from_le_bytes(data.try_into().unwrap()),
-            None => i32::from_be_bytes(data.try_into().unwrap()),
-        }
+    pub fn read_i32(&mut self, endianness: Option<Endianness>) -> Result<i32, BinaryError> {
+        let data = self.read_array("i32")?;
+        Ok(match endianness {
+            Some(Endianness::Big) => i32::from_be_bytes(data),
+            Some(Endianness::Little) => i32::from_le_bytes(data),
+            None => i32::from_be_bytes(data),
+        })
     }
 
     pub fn write_i32(&mut self, data: i32, endianness: Option<Endianness>) {
@@ -203,13 +208,13 @@ impl BinaryStream {
         self.write(bytes.to_vec());
     }
 
-    pub fn read_i64(&mut self, endianness: Option<Endianness>) -> i64 {
-        let bytes = self.read(8);
-        match endianness {
-            Some(Endianness::Big) => i64::from_be_bytes(bytes.try_into().unwrap()),
-            Some(Endianness::Little) => i64::from_le_bytes(bytes.try_into().unwrap()),
-            None => i64::from_be_bytes(bytes.try_into().unwrap()),
-        }
+    pub fn read_i64(&mut self, endianness: Option<Endianness>) -> Result<i64, BinaryError> {
+        let bytes = self.read_array("i64")?;
+        Ok(match endianness {
+            Some(Endianness::Big) => i64::from_be_bytes(bytes),
+            Some(Endianness::Little) => i64::from_le_bytes(bytes),
+            None => i64::from_be_bytes(bytes),
+        })
     }
 
     pub fn write_i64(&mut self, data: i64, endianness: Option<Endianness>) {
@@ -221,7 +226,7 @@ impl BinaryStream {
         self.write(bytes.to_vec());
     }
 
-    pub fn read_long(&mut self, endianness: Option<Endianness>) -> i64 {
+    pub fn read_long(&mut self, endianness: Option<Endianness>) -> Result<i64, BinaryError> {
         self.read_i64(endianness)
     }
 
@@ -229,7 +234,7 @@ impl BinaryStream {
         self.write_i64(data, endianness);
     }
 
-    pub fn read_short(&mut self, endianness: Option<Endianness>) -> u16 {
+    pub fn read_short(&mut self, endianness: Option<Endianness>) -> Result<u16, BinaryError> {
         self.read_u16(endianness)
     }
 
@@ -237,13 +242,10 @@ impl BinaryStream {
         self.write_u16(data, endianness);
     }
 
-    pub fn read_string16(&mut self, endianness: Option<Endianness>) -> Result<String, String> {
-        let length = self.read_u16(endianness);
-        let data = self.read(length as usize);
-        match String::from_utf8(data) {
-            Ok(string) => Ok(string),
-            Err(e) => Err(format!("Invalid UTF-8 sequence: {}", e))
-        }
+    pub fn read_string16(&mut self, endianness: Option<Endianness>) -> Result<String, BinaryError> {
+        let length = self.read_u16(endianness).map_err(|e| e.with_field("string16"))?;
+        let data = self.read_field(length as usize, "string16")?;
+        String::from_utf8(data).map_err(|_| BinaryError::InvalidUtf8 { field: "string16" })
     }
 
     pub fn write_string16(&mut self, data: &str, endianness: Option<Endianness>) {
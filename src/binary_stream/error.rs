@@ -0,0 +1,63 @@
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BinaryError {
+    OutOfBounds {
+        field: &'static str,
+        wanted: usize,
+        available: usize,
+    },
+    InvalidUtf8 {
+        field: &'static str,
+    },
+    /// A count read off the wire is larger than we are willing to allocate for.
+    TooLarge {
+        field: &'static str,
+        count: usize,
+        limit: usize,
+    },
+}
+
+impl BinaryError {
+    pub fn field(&self) -> &'static str {
+        match self {
+            Self::OutOfBounds { field, .. } => field,
+            Self::InvalidUtf8 { field } => field,
+            Self::TooLarge { field, .. } => field,
+        }
+    }
+
+    pub fn with_field(self, name: &'static str) -> Self {
+        match self {
+            Self::OutOfBounds { wanted, available, .. } => Self::OutOfBounds { field: name, wanted, available },
+            Self::InvalidUtf8 { .. } => Self::InvalidUtf8 { field: name },
+            Self::TooLarge { count, limit, .. } => Self::TooLarge { field: name, count, limit },
+        }
+    }
+}
+
+impl fmt::Display for BinaryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OutOfBounds { field, wanted, available } => write!(
+                f, "Not enough bytes to read {}: wanted {}, {} available", field, wanted, available
+            ),
+            Self::InvalidUtf8 { field } => write!(f, "Invalid UTF-8 sequence in {}", field),
+            Self::TooLarge { field, count, limit } => write!(f, "Too many {}: {} exceeds the limit of {}", field, count, limit),
+        }
+    }
+}
+
+impl std::error::Error for BinaryError {}
+
+/// Names the packet field a failed read belongs to, so the error says
+/// `mtu_size` rather than the primitive (`u16`) that ran out of bytes.
+pub trait FieldContext<T> {
+    fn field(self, name: &'static str) -> Result<T, BinaryError>;
+}
+
+impl<T> FieldContext<T> for Result<T, BinaryError> {
+    fn field(self, name: &'static str) -> Result<T, BinaryError> {
+        self.map_err(|e| e.with_field(name))
+    }
+}
@@ -1,5 +1,8 @@
+#[allow(clippy::module_inception)]
 mod binary_stream;
 mod endianess;
+mod error;
 
 pub use binary_stream::*;
-pub use endianess::*;
\ No newline at end of file
+pub use endianess::*;
+pub use error::*;
@@ -1,114 +1,274 @@
 #![allow(dead_code)]
 
-use chrono::Utc;
-use std::sync::Arc;
 use std::sync::mpsc::{channel, Sender, Receiver};
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
 
 use crate::proto::{ 
-    Ack, Address, ConnectionReplyOne, ConnectionReplyTwo, ConnectionRequestOne, ConnectionRequestTwo, FrameSet, Nack, UnconnectedPing, UnconnectedPong
+    Ack, Address, AlreadyConnected, ConnectedPing, ConnectionAttemptFailed, ConnectionBanned, ConnectedPong, ConnectionReplyOne, ConnectionReplyTwo, ConnectionRequest, ConnectionRequestAccepted, ConnectionRequestOne, ConnectionRequestTwo, DisconnectionNotification, Frame, FrameSet, IncompatibleProtocolVersion, IpRecentlyConnected, Nack, NewIncommingConnection, NoFreeIncomingConnections, UnconnectedPing, UnconnectedPong
 };
-use crate::socket::Socket;
-use crate::Priority;
+use crate::error::RakError;
+use crate::socket::{Socket, Transport};
+use crate::clock::{Clock, SystemClock};
+use crate::framer::{Framer, Latency, Receipt};
+use crate::{Priority, Reliability};
 
-use super::Framer;
+use super::{ClientConfig, ClientEvent, ConnectFailedReason, ConnectionState, DisconnectReason};
 
-#[derive(Clone, Debug)]
-pub struct Event {
-    pub name: String,
-    pub data: Vec<u8>,
-}
-
-pub struct Client {
-    pub socket: Arc<Socket>,
-    pub guid: i64,
+pub struct Client<T: Transport = Socket> {
+    pub transport: T,
+    pub config: ClientConfig,
     pub mtu_size: u16,
     pub framer: Framer,
-    pub tick_count: u32,
-    pub event_sender: Sender<Event>,
-    pub event_receiver: Receiver<Event>,
-    pub connected: AtomicBool,
+    pub event_sender: Sender<ClientEvent>,
+    pub event_receiver: Receiver<ClientEvent>,
+    pub state: ConnectionState,
+    pub connect_failure: Option<ConnectFailedReason>,
+    pub server_guid: i64,
+    pub protocol_adopted: bool,
+    pub handshake_attempts: u32,
+    pub last_handshake_at: u64,
+    pub last_ping_at: u64,
+    pub latency: Latency,
+    pub last_received_at: u64,
+    pub clock: Box<dyn Clock>,
 }
 
-impl Client {
-    pub fn new(host: String, port: u16) -> Self {
-        let guid = 4124124124124;
-        let mtu_size = 1492;
-        let socket = Arc::new(Socket::new(host, port));
+impl Client<Socket> {
+    pub fn new(host: String, port: u16) -> Result<Self, RakError> {
+        Self::with_config(host, port, ClientConfig::default())
+    }
+
+    pub fn with_config(host: String, port: u16, config: ClientConfig) -> Result<Self, RakError> {
+        config.validate()?;
+        let socket = Socket::connect(&config.bind_address, host, port)?;
+        Self::with_transport(socket, config)
+    }
+}
+
+impl<T: Transport> Client<T> {
+    pub fn with_transport(transport: T, config: ClientConfig) -> Result<Self, RakError> {
+        config.validate()?;
+        let mtu_size = config.initial_mtu_size();
         let (event_sender, event_receiver) = channel();
-        let framer = Framer::new(
-            Arc::clone(&socket), 
-            mtu_size, 
-            guid, 
-            event_sender.clone()
-        );
+        let mut framer = Framer::new(mtu_size);
+        framer.split_limits = config.split_limits;
         
-        Self { 
-            socket, 
-            guid, 
+        Ok(Self { 
+            transport, 
+            config,
             mtu_size, 
             framer,
-            tick_count: 0,
             event_sender,
             event_receiver,
-            connected: AtomicBool::new(false),
+            state: ConnectionState::Unconnected,
+            connect_failure: None,
+            server_guid: 0,
+            protocol_adopted: false,
+            handshake_attempts: 0,
+            last_handshake_at: 0,
+            last_ping_at: 0,
+            latency: Latency::default(),
+            last_received_at: 0,
+            clock: Box::new(SystemClock::new()),
+        })
+    }
+
+    pub fn connect(&mut self) -> Result<(), RakError> {
+        self.config.validate()?;
+        self.mtu_size = self.config.initial_mtu_size();
+        self.framer = Framer::new(self.mtu_size);
+        self.framer.split_limits = self.config.split_limits;
+        self.connect_failure = None;
+        self.protocol_adopted = false;
+        self.ping()?;
+        self.set_state(ConnectionState::OpenRequest1Sent);
+        self.send_handshake()
+    }
+
+    /// The MTU to probe with on the current `ConnectionRequestOne` attempt. Each size
+    /// in `mtu_sizes` gets `mtu_attempts_per_size` tries before falling back to the next.
+    pub fn discovery_mtu_size(&self) -> u16 {
+        let attempt = self.handshake_attempts.saturating_sub(1) / self.config.mtu_attempts_per_size.max(1);
+        let index = (attempt as usize).min(self.config.mtu_sizes.len().saturating_sub(1));
+        self.config.mtu_sizes.get(index).copied().unwrap_or(self.mtu_size)
+    }
+
+    pub fn max_handshake_attempts(&self) -> u32 {
+        match self.state {
+            ConnectionState::OpenRequest1Sent => {
+                self.config.mtu_sizes.len().max(1) as u32 * self.config.mtu_attempts_per_size.max(1)
+            }
+            _ => self.config.handshake_max_retries + 1,
         }
     }
 
-    pub fn connect(&mut self) -> Result<(), String> {
-        if self.tick_count > 0 { 
-            self.mtu_size = 1200;
+    /// Agrees on the MTU the server replied with, kept within our configured limits.
+    pub fn apply_mtu_size(&mut self, mtu_size: u16) {
+        let mtu_size = mtu_size.clamp(self.config.min_mtu_size, self.config.max_mtu_size);
+        self.mtu_size = mtu_size;
+        self.framer.set_mtu_size(mtu_size);
+    }
+
+    pub fn set_state(&mut self, state: ConnectionState) {
+        self.state = state;
+        self.handshake_attempts = 0;
+    }
+
+    /// Sends the packet the current handshake stage is waiting on an answer to.
+    pub fn send_handshake(&mut self) -> Result<(), RakError> {
+        self.handshake_attempts += 1;
+        self.last_handshake_at = self.now();
+
+        match self.state {
+            ConnectionState::OpenRequest1Sent => {
+                let mut request = ConnectionRequestOne::new(self.config.protocol, self.discovery_mtu_size());
+                request.magic = self.config.magic;
+                self.send_raw(request.serialize())
+            }
+            ConnectionState::OpenRequest2Sent => {
+                let mut request = ConnectionRequestTwo::new(
+                    Address::from_socket_address(&self.transport.peer_address()?), 
+                    self.mtu_size, 
+                    self.config.guid
+                );
+                request.magic = self.config.magic;
+                self.send_raw(request.serialize())
+            }
+            // The request goes out reliably and the framer resends it until it is
+            // acknowledged, so later attempts only count towards the timeout.
+            ConnectionState::ConnectionRequestSent if self.handshake_attempts == 1 => self.send_connect(),
+            _ => Ok(()),
         }
-        let timestamp = Utc::now().timestamp();
-        let ping = UnconnectedPing::new(timestamp, self.guid);
-        self.socket.send(ping.serialize()).unwrap();
-        let request = ConnectionRequestOne::new(11, self.mtu_size);
-        self.socket.send(request.serialize()).unwrap();
-        Ok(())
     }
 
-    pub fn ping(&mut self) {
-        let timestamp = Utc::now().timestamp();
-        let ping = UnconnectedPing::new(timestamp, self.guid);
-        self.socket.send(ping.serialize()).unwrap();
+    pub fn ping(&mut self) -> Result<(), RakError> {
+        let mut ping = UnconnectedPing::new(self.now() as i64, self.config.guid);
+        ping.magic = self.config.magic;
+        self.transport.send(&ping.serialize())?;
+        Ok(())
     }
 
-    pub fn receive(&mut self) -> Result<Vec<u8>, String> {
-        let mut buffer = [0; 1492];
-        match self.socket.receive(&mut buffer) {
-            Ok(0) => Ok(vec![]),
-            Ok(size) => {
-                self.handle_packet(&buffer[..size]);
+    pub fn receive(&mut self) -> Result<Vec<u8>, RakError> {
+        let mut buffer = vec![0; self.config.max_mtu_size.max(1500) as usize];
+        match self.transport.try_receive(&mut buffer)? {
+            None => Ok(vec![]),
+            Some(size) => {
+                self.last_received_at = self.now();
+                self.handle_packet(&buffer[..size])?;
                 Ok(buffer[..size].to_vec())
             }
-            Err(e) => Err(e.to_string())
         }
     }
 
-    pub fn tick(&mut self) {
+    pub fn tick(&mut self) -> Result<(), RakError> {
         // let _ = self.receive();
-        self.framer.tick();
+        let now = self.now();
+        if self.state.is_handshaking() && now.saturating_sub(self.last_handshake_at) >= self.config.handshake_retry_interval {
+            if self.handshake_attempts >= self.max_handshake_attempts() {
+                return self.fail_connect(ConnectFailedReason::Timeout(self.state));
+            }
+            self.send_handshake()?;
+        }
+        if self.state == ConnectionState::Connected && now.saturating_sub(self.last_received_at) >= self.config.session_timeout {
+            self.set_state(ConnectionState::Disconnected);
+            self.emit_event(ClientEvent::Disconnected { reason: DisconnectReason::Timeout });
+            return Ok(());
+        }
+        if self.state == ConnectionState::Connected && now.saturating_sub(self.last_ping_at) >= self.config.ping_interval {
+            self.send_connected_ping()?;
+        }
+        self.framer.tick(now);
+        self.flush()
+    }
+
+    /// Gives up on the handshake. Besides the `ConnectFailed` event, the reason
+    /// comes back as an error from the `tick` or `receive` that noticed it.
+    pub fn fail_connect(&mut self, reason: ConnectFailedReason) -> Result<(), RakError> {
+        self.set_state(ConnectionState::Disconnected);
+        self.connect_failure = Some(reason);
+        self.emit_event(ClientEvent::ConnectFailed { reason });
+        Err(reason.into())
+    }
+
+    /// Offline rejections only mean something while we are still handshaking.
+    fn reject(&mut self, reason: ConnectFailedReason) -> Result<(), RakError> {
+        if self.state.is_handshaking() {
+            return self.fail_connect(reason);
+        }
+        Ok(())
+    }
+
+    pub fn now(&self) -> u64 {
+        self.clock.now()
+    }
+
+    /// Sends whatever the framer queued and reports receipts it has settled.
+    pub fn flush(&mut self) -> Result<(), RakError> {
+        while let Some(datagram) = self.framer.poll_datagram() {
+            self.transport.send(&datagram)?;
+        }
+        while let Some(receipt) = self.framer.poll_receipt() {
+            match receipt {
+                Receipt::Acked(receipt) => self.emit_event(ClientEvent::ReceiptAcked { receipt }),
+                Receipt::Lost(receipt) => self.emit_event(ClientEvent::ReceiptLost { receipt }),
+            }
+        }
+        while let Some(drop) = self.framer.poll_split_drop() {
+            self.emit_event(ClientEvent::SplitDropped { split_id: drop.split_id, reason: drop.reason });
+        }
+        Ok(())
     }
 
-    pub fn send(&mut self, data: Vec<u8>) -> Result<(), String> {
-        self.socket.send(data).unwrap();
+    pub fn send_raw(&mut self, data: Vec<u8>) -> Result<(), RakError> {
+        self.transport.send(&data)?;
         Ok(())
     }
 
-    pub fn emit_event(&self, name: &str, data: Vec<u8>) {
-        let event = Event {
-            name: name.to_string(),
-            data,
-        };
+    pub fn emit_event(&self, event: ClientEvent) {
         self.event_sender.send(event).unwrap();
     }
 
-    pub fn frame_and_send(&mut self, data: Vec<u8>) {
-        let mut frame = self.framer.pls_frame(data);
-        self.framer.send_frame(&mut frame, Some(Priority::Immediate));
+    /// Queues a game packet with the given delivery guarantees. Only `Immediate`
+    /// goes out straight away; the other levels are packed on the next `tick`.
+    ///
+    /// Returns a receipt number. With the ack-receipt reliabilities it comes back
+    /// in a `ClientEvent::ReceiptAcked` or `ClientEvent::ReceiptLost`.
+    pub fn send(&mut self, data: Vec<u8>, reliability: Reliability, channel: u8, priority: Priority) -> Result<u32, RakError> {
+        let mut frame = Frame::new();
+        frame.reliability = reliability;
+        frame.order_channel = Some(channel);
+        frame.payload = data;
+        self.framer.current_time = self.now();
+        let receipt = self.framer.send_frame(&mut frame, Some(priority))?;
+        self.flush()?;
+        Ok(receipt)
+    }
+
+    pub fn frame_and_send(&mut self, data: Vec<u8>) -> Result<(), RakError> {
+        self.send(data, Reliability::ReliableOrdered, 0, Priority::Immediate)?;
+        Ok(())
+    }
+
+    pub fn send_connected_ping(&mut self) -> Result<(), RakError> {
+        self.last_ping_at = self.now();
+        let ping = ConnectedPing::new(self.last_ping_at as i64);
+        self.send(ping.serialize(), Reliability::Unreliable, 0, Priority::Immediate)?;
+        Ok(())
+    }
+
+    pub fn latency(&self) -> Latency {
+        self.latency
     }
 
-    pub fn handle_packet(&mut self, binary: &[u8]) {
+    pub fn send_connect(&mut self) -> Result<(), RakError> {
+        let packet = ConnectionRequest::new(self.config.guid, self.now() as i64, false);
+        self.frame_and_send(packet.serialize())
+    }
+
+    pub fn handle_packet(&mut self, binary: &[u8]) -> Result<(), RakError> {
+        if binary.is_empty() {
+            return Ok(());
+        }
         let mut packet_id = binary[0];
         if (packet_id & 0xf0) == 0x80 {
             packet_id = FrameSet::ID;
@@ -116,55 +276,174 @@ impl Client {
 
         match packet_id {
             UnconnectedPong::ID => {
-                // let packet = UnconnectedPong::deserialize(binary.to_vec()).unwrap();
-                self.emit_event("unconnected_pong", binary.to_vec());
+                let packet = UnconnectedPong::deserialize(binary.to_vec())?;
+                self.emit_event(ClientEvent::Pong { server_guid: packet.guid as i64, motd: packet.message });
             }
             ConnectionReplyOne::ID => {
-                let packet = ConnectionReplyOne::deserialize(binary.to_vec()).unwrap();
-                self.emit_event("connection_reply_one", binary.to_vec());
-                let request = ConnectionRequestTwo::new(
-                    Address::new(4, self.socket.server_address.clone(), self.socket.server_port), 
-                    packet.mtu_size, 
-                    self.guid
-                );
-                self.send(request.serialize()).unwrap();
-                if packet.mtu_size > 1500 || packet.mtu_size < 400 {
-                    self.connect().unwrap();
-                } 
+                if self.state != ConnectionState::OpenRequest1Sent {
+                    return Ok(());
+                }
+                let packet = ConnectionReplyOne::deserialize(binary.to_vec())?;
+                self.server_guid = packet.guid;
+                self.apply_mtu_size(packet.mtu_size);
+                self.set_state(ConnectionState::OpenRequest2Sent);
+                self.emit_event(ClientEvent::HandshakeProgress { state: self.state, mtu: self.mtu_size });
+                self.send_handshake()?;
             }
             ConnectionReplyTwo::ID => {
-                let _packet = ConnectionReplyTwo::deserialize(binary.to_vec()).unwrap();
-                self.emit_event("connection_reply_two", binary.to_vec());
-                self.connected.store(true, Ordering::SeqCst);
-                self.framer.send_connect();
+                if self.state != ConnectionState::OpenRequest2Sent {
+                    return Ok(());
+                }
+                let packet = ConnectionReplyTwo::deserialize(binary.to_vec())?;
+                self.server_guid = packet.guid;
+                self.apply_mtu_size(packet.mtu_size);
+                self.set_state(ConnectionState::ConnectionRequestSent);
+                self.emit_event(ClientEvent::HandshakeProgress { state: self.state, mtu: self.mtu_size });
+                self.send_handshake()?;
+            }
+            FrameSet::ID | Ack::ID | Nack::ID => {
+                let now = self.now();
+                self.framer.receive(binary, now)?;
+                while let Some(frame) = self.framer.poll_message() {
+                    self.handle_message(&frame)?;
+                }
+                self.flush()?;
             }
-            FrameSet::ID => {
-                let packet = FrameSet::deserialize(&binary).unwrap();
-                self.framer.on_frameset(&packet);
+            IncompatibleProtocolVersion::ID => {
+                let packet = IncompatibleProtocolVersion::deserialize(binary)?;
+                let retry = self.config.adopt_server_protocol
+                    && !self.protocol_adopted
+                    && packet.protocol != self.config.protocol
+                    && self.state == ConnectionState::OpenRequest1Sent;
+                if retry {
+                    self.config.protocol = packet.protocol;
+                    self.protocol_adopted = true;
+                    self.emit_event(ClientEvent::ProtocolChanged { protocol: packet.protocol });
+                    self.set_state(ConnectionState::OpenRequest1Sent);
+                    self.send_handshake()?;
+                } else {
+                    self.reject(ConnectFailedReason::IncompatibleProtocol { server_protocol: packet.protocol })?;
+                }
             }
-            Ack::ID => {
-                self.emit_event("ack", binary.to_vec());
-                let frame = self.framer.pls_frame(binary.to_vec());
-                self.framer.handle_packet(&frame);
+            ConnectionBanned::ID => {
+                ConnectionBanned::deserialize(binary)?;
+                self.reject(ConnectFailedReason::Banned)?;
             }
-            Nack::ID => {
-                self.emit_event("nack", binary.to_vec());
-                let frame = self.framer.pls_frame(binary.to_vec());
-                self.framer.handle_packet(&frame);
+            NoFreeIncomingConnections::ID => {
+                NoFreeIncomingConnections::deserialize(binary)?;
+                self.reject(ConnectFailedReason::NoFreeIncomingConnections)?;
             }
-            21 => { 
-                self.connected.store(false, Ordering::SeqCst);
-                self.emit_event("disconnect", binary.to_vec());
+            AlreadyConnected::ID => {
+                AlreadyConnected::deserialize(binary)?;
+                self.reject(ConnectFailedReason::AlreadyConnected)?;
+            }
+            ConnectionAttemptFailed::ID => {
+                ConnectionAttemptFailed::deserialize(binary)?;
+                self.reject(ConnectFailedReason::ConnectionAttemptFailed)?;
+            }
+            IpRecentlyConnected::ID => {
+                IpRecentlyConnected::deserialize(binary)?;
+                self.reject(ConnectFailedReason::IpRecentlyConnected)?;
+            }
+            DisconnectionNotification::ID => { 
+                self.set_state(ConnectionState::Disconnected);
+                self.emit_event(ClientEvent::Disconnected { reason: DisconnectReason::ServerRequested });
             }
             _ => {
-                println!("Received unknown packet: {}", packet_id);
-                self.emit_event("unknown_packet", vec![packet_id]);
+                self.emit_event(ClientEvent::UnknownPacket { id: packet_id, payload: binary.to_vec() });
             }
         }
+        Ok(())
+    }
+
+    pub fn handle_message(&mut self, frame: &Frame) -> Result<(), RakError> {
+        if frame.payload.is_empty() {
+            return Ok(());
+        }
+        let packet_id = frame.payload[0];
+
+        match packet_id {
+            ConnectedPing::ID => {
+                let packet = ConnectedPing::deserialize(&frame.payload)?;
+                let pong = ConnectedPong::new(packet.timestamp, self.now() as i64);
+                self.frame_and_send(pong.serialize())?;
+            }
+            ConnectedPong::ID => {
+                let packet = ConnectedPong::deserialize(&frame.payload)?;
+                let now = self.now() as i64;
+                if packet.ping_timestamp >= 0 && packet.ping_timestamp <= now {
+                    self.latency.update((now - packet.ping_timestamp) as u64);
+                    self.emit_event(ClientEvent::LatencyUpdated { rtt: self.latency.rtt });
+                }
+            }
+            ConnectionRequestAccepted::ID => {
+                if self.state != ConnectionState::ConnectionRequestSent {
+                    return Ok(());
+                }
+                let packet = ConnectionRequestAccepted::deserialize(&frame.payload)?;
+                let server_address = Address::from_socket_address(&self.transport.peer_address()?);
+                let local_port = self.transport.local_address()?.port();
+                let internal_addresses = core::array::from_fn(|_| Address::new(4, "127.0.0.1".to_string(), local_port));
+                let response = NewIncommingConnection::new(
+                    server_address, 
+                    internal_addresses, 
+                    self.now() as i64, 
+                    packet.server_send_time
+                );
+                self.frame_and_send(response.serialize())?;
+                self.set_state(ConnectionState::Connected);
+                self.emit_event(ClientEvent::Connected { mtu: self.mtu_size, server_guid: self.server_guid });
+                self.send_connected_ping()?;
+            }
+            DisconnectionNotification::ID => {
+                self.set_state(ConnectionState::Disconnected);
+                self.emit_event(ClientEvent::Disconnected { reason: DisconnectReason::ServerRequested });
+            }
+            _ => {
+                self.emit_event(ClientEvent::Message {
+                    channel: frame.order_channel.unwrap_or(0),
+                    reliability: frame.reliability,
+                    payload: frame.payload.to_vec(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Tells the server we are leaving and waits up to `disconnect_timeout` for it to
+    /// acknowledge, so it can drop the session instead of waiting for it to time out.
+    pub fn disconnect(&mut self) -> Result<(), RakError> {
+        match self.state {
+            ConnectionState::Connected => {
+                self.set_state(ConnectionState::Disconnecting);
+                let notification = DisconnectionNotification::new();
+                self.frame_and_send(notification.serialize())?;
+                let now = self.now();
+                self.framer.tick(now);
+                self.flush()?;
+
+                // This wait blocks the caller, so it runs on wall time even under a mock clock.
+                let waiting_since = Instant::now();
+                let timeout = Duration::from_millis(self.config.disconnect_timeout);
+                while self.framer.has_pending() && waiting_since.elapsed() < timeout {
+                    if self.receive()?.is_empty() {
+                        std::thread::sleep(Duration::from_millis(10));
+                    }
+                    self.framer.tick(self.now());
+                    self.flush()?;
+                }
+            }
+            state if state.is_handshaking() => {}
+            _ => return Err(RakError::NotConnected),
+        }
+
+        self.set_state(ConnectionState::Disconnected);
+        self.emit_event(ClientEvent::Disconnected { reason: DisconnectReason::ClientRequested });
+        Ok(())
     }
 
     pub fn is_connected(&self) -> bool {
-        self.connected.load(Ordering::SeqCst)
+        self.state == ConnectionState::Connected
     }
 }
 
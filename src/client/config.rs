@@ -0,0 +1,140 @@
+use crate::error::RakError;
+use crate::framer::{SplitLimits, MIN_MTU_SIZE};
+use crate::proto::MAGIC;
+
+/// Everything about a client that can be chosen before connecting.
+///
+/// Start from `ClientConfig::default()` (which picks a random GUID) and chain
+/// the setters for whatever should differ.
+#[derive(Debug, Clone)]
+pub struct ClientConfig {
+    pub guid: i64,
+    pub protocol: u8,
+    /// Retry once with the server's protocol version if it rejects ours.
+    pub adopt_server_protocol: bool,
+    /// MTU sizes to probe during the handshake, largest first.
+    pub mtu_sizes: Vec<u16>,
+    pub mtu_attempts_per_size: u32,
+    pub min_mtu_size: u16,
+    pub max_mtu_size: u16,
+    pub bind_address: String,
+    pub handshake_retry_interval: u64,
+    pub handshake_max_retries: u32,
+    pub disconnect_timeout: u64,
+    pub session_timeout: u64,
+    pub ping_interval: u64,
+    pub split_limits: SplitLimits,
+    pub magic: [u8; 16],
+}
+
+impl Default for ClientConfig {
+    fn default() -> Self {
+        Self {
+            guid: rand::random::<i64>(),
+            protocol: 11,
+            adopt_server_protocol: true,
+            mtu_sizes: vec![1492, 1200, 576],
+            mtu_attempts_per_size: 4,
+            min_mtu_size: 400,
+            max_mtu_size: 1492,
+            bind_address: "0.0.0.0:0".to_string(),
+            handshake_retry_interval: 1000,
+            handshake_max_retries: 5,
+            disconnect_timeout: 500,
+            session_timeout: 10000,
+            ping_interval: 5000,
+            split_limits: SplitLimits::default(),
+            magic: MAGIC,
+        }
+    }
+}
+
+impl ClientConfig {
+    pub fn guid(mut self, guid: i64) -> Self {
+        self.guid = guid;
+        self
+    }
+
+    pub fn protocol(mut self, protocol: u8) -> Self {
+        self.protocol = protocol;
+        self
+    }
+
+    pub fn adopt_server_protocol(mut self, adopt: bool) -> Self {
+        self.adopt_server_protocol = adopt;
+        self
+    }
+
+    pub fn mtu_sizes(mut self, mtu_sizes: Vec<u16>) -> Self {
+        self.mtu_sizes = mtu_sizes;
+        self
+    }
+
+    pub fn mtu_attempts_per_size(mut self, attempts: u32) -> Self {
+        self.mtu_attempts_per_size = attempts;
+        self
+    }
+
+    pub fn mtu_limits(mut self, min_mtu_size: u16, max_mtu_size: u16) -> Self {
+        self.min_mtu_size = min_mtu_size;
+        self.max_mtu_size = max_mtu_size;
+        self
+    }
+
+    pub fn bind_address(mut self, bind_address: impl Into<String>) -> Self {
+        self.bind_address = bind_address.into();
+        self
+    }
+
+    pub fn handshake_retry_interval(mut self, milliseconds: u64) -> Self {
+        self.handshake_retry_interval = milliseconds;
+        self
+    }
+
+    pub fn handshake_max_retries(mut self, retries: u32) -> Self {
+        self.handshake_max_retries = retries;
+        self
+    }
+
+    pub fn disconnect_timeout(mut self, milliseconds: u64) -> Self {
+        self.disconnect_timeout = milliseconds;
+        self
+    }
+
+    pub fn session_timeout(mut self, milliseconds: u64) -> Self {
+        self.session_timeout = milliseconds;
+        self
+    }
+
+    pub fn ping_interval(mut self, milliseconds: u64) -> Self {
+        self.ping_interval = milliseconds;
+        self
+    }
+
+    pub fn split_limits(mut self, split_limits: SplitLimits) -> Self {
+        self.split_limits = split_limits;
+        self
+    }
+
+    pub fn magic(mut self, magic: [u8; 16]) -> Self {
+        self.magic = magic;
+        self
+    }
+
+    /// Checks the MTU settings: the limits must be ordered and no lower than
+    /// `MIN_MTU_SIZE`, and every size to probe must fall within them.
+    pub fn validate(&self) -> Result<(), RakError> {
+        if self.min_mtu_size < MIN_MTU_SIZE || self.min_mtu_size > self.max_mtu_size {
+            return Err(RakError::InvalidMtuLimits { min: self.min_mtu_size, max: self.max_mtu_size });
+        }
+        if let Some(&size) = self.mtu_sizes.iter().find(|&&size| size < self.min_mtu_size || size > self.max_mtu_size) {
+            return Err(RakError::InvalidMtuSize(size));
+        }
+        Ok(())
+    }
+
+    /// The MTU assumed before the handshake has agreed on one.
+    pub fn initial_mtu_size(&self) -> u16 {
+        self.mtu_sizes.first().copied().unwrap_or(self.max_mtu_size)
+    }
+}
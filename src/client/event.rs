@@ -0,0 +1,64 @@
+use crate::framer::SplitDropReason;
+use crate::Reliability;
+
+use super::{ConnectFailedReason, ConnectionState};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DisconnectReason {
+    /// Nothing arrived from the server within `session_timeout`.
+    Timeout,
+    ClientRequested,
+    ServerRequested,
+}
+
+impl DisconnectReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Timeout => "timeout",
+            Self::ClientRequested => "client_requested",
+            Self::ServerRequested => "server_requested",
+        }
+    }
+}
+
+/// Everything a `Client` reports through its event channel.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ClientEvent {
+    /// An offline pong, usually the answer to `ping`.
+    Pong { server_guid: i64, motd: String },
+    /// The server answered a handshake step and the client moved on to `state`.
+    HandshakeProgress { state: ConnectionState, mtu: u16 },
+    /// The server rejected our protocol version and we are retrying with its own.
+    ProtocolChanged { protocol: u8 },
+    Connected { mtu: u16, server_guid: i64 },
+    ConnectFailed { reason: ConnectFailedReason },
+    Disconnected { reason: DisconnectReason },
+    /// An application packet delivered by the reliability layer.
+    Message { channel: u8, reliability: Reliability, payload: Vec<u8> },
+    LatencyUpdated { rtt: f64 },
+    ReceiptAcked { receipt: u32 },
+    ReceiptLost { receipt: u32 },
+    SplitDropped { split_id: u16, reason: SplitDropReason },
+    /// An offline packet the client does not handle.
+    UnknownPacket { id: u8, payload: Vec<u8> },
+}
+
+impl ClientEvent {
+    /// The snake_case tag of the variant, as used by the Node binding.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Pong { .. } => "pong",
+            Self::HandshakeProgress { .. } => "handshake_progress",
+            Self::ProtocolChanged { .. } => "protocol_changed",
+            Self::Connected { .. } => "connected",
+            Self::ConnectFailed { .. } => "connect_failed",
+            Self::Disconnected { .. } => "disconnected",
+            Self::Message { .. } => "message",
+            Self::LatencyUpdated { .. } => "latency_updated",
+            Self::ReceiptAcked { .. } => "receipt_acked",
+            Self::ReceiptLost { .. } => "receipt_lost",
+            Self::SplitDropped { .. } => "split_dropped",
+            Self::UnknownPacket { .. } => "unknown_packet",
+        }
+    }
+}
@@ -1,5 +1,10 @@
+#[allow(clippy::module_inception)]
 mod client;
-mod framer;
+mod config;
+mod event;
+mod state;
 
 pub use client::*;
-pub use framer::*;
+pub use config::*;
+pub use event::*;
+pub use state::*;
@@ -0,0 +1,56 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectionState {
+    Unconnected,
+    OpenRequest1Sent,
+    OpenRequest2Sent,
+    ConnectionRequestSent,
+    Connected,
+    Disconnecting,
+    Disconnected,
+}
+
+impl ConnectionState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Unconnected => "unconnected",
+            Self::OpenRequest1Sent => "open_request_1_sent",
+            Self::OpenRequest2Sent => "open_request_2_sent",
+            Self::ConnectionRequestSent => "connection_request_sent",
+            Self::Connected => "connected",
+            Self::Disconnecting => "disconnecting",
+            Self::Disconnected => "disconnected",
+        }
+    }
+
+    /// Whether the client is still waiting on the server to answer a handshake step.
+    pub fn is_handshaking(&self) -> bool {
+        matches!(self, Self::OpenRequest1Sent | Self::OpenRequest2Sent | Self::ConnectionRequestSent)
+    }
+}
+
+/// Why a `connect` attempt ended without a session.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectFailedReason {
+    /// The server never answered the given handshake stage.
+    Timeout(ConnectionState),
+    IncompatibleProtocol { server_protocol: u8 },
+    Banned,
+    NoFreeIncomingConnections,
+    AlreadyConnected,
+    ConnectionAttemptFailed,
+    IpRecentlyConnected,
+}
+
+impl ConnectFailedReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Timeout(_) => "timeout",
+            Self::IncompatibleProtocol { .. } => "incompatible_protocol",
+            Self::Banned => "banned",
+            Self::NoFreeIncomingConnections => "no_free_incoming_connections",
+            Self::AlreadyConnected => "already_connected",
+            Self::ConnectionAttemptFailed => "connection_attempt_failed",
+            Self::IpRecentlyConnected => "ip_recently_connected",
+        }
+    }
+}
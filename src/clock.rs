@@ -0,0 +1,60 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+
+/// Milliseconds on a monotonic timeline. All timers, RTT samples and packet
+/// timestamps are read from one of these.
+pub trait Clock: Send {
+    fn now(&self) -> u64;
+}
+
+/// Real time, counted from when the clock was created.
+#[derive(Debug, Clone, Copy)]
+pub struct SystemClock {
+    pub started_at: Instant,
+}
+
+impl SystemClock {
+    pub fn new() -> Self {
+        Self { started_at: Instant::now() }
+    }
+}
+
+impl Default for SystemClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> u64 {
+        self.started_at.elapsed().as_millis() as u64
+    }
+}
+
+/// A clock that only moves when told to. Clones share the same time, so a test
+/// can keep one handle and give another to the client.
+#[derive(Debug, Clone, Default)]
+pub struct MockClock {
+    pub time: Arc<AtomicU64>,
+}
+
+impl MockClock {
+    pub fn new(start: u64) -> Self {
+        Self { time: Arc::new(AtomicU64::new(start)) }
+    }
+
+    pub fn advance(&self, milliseconds: u64) {
+        self.time.fetch_add(milliseconds, Ordering::SeqCst);
+    }
+
+    pub fn set(&self, milliseconds: u64) {
+        self.time.store(milliseconds, Ordering::SeqCst);
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> u64 {
+        self.time.load(Ordering::SeqCst)
+    }
+}
@@ -0,0 +1,76 @@
+use std::fmt;
+use std::net::SocketAddr;
+
+use crate::{BinaryError, ConnectFailedReason, ConnectionState};
+
+#[derive(Debug)]
+pub enum RakError {
+    /// The underlying socket failed to send or receive.
+    Io(std::io::Error),
+    /// A packet was too short or otherwise could not be decoded.
+    Decode(BinaryError),
+    /// A datagram arrived with an id we do not handle in this state.
+    UnexpectedPacket(u8),
+    /// The server stopped answering the handshake at this stage.
+    Timeout(ConnectionState),
+    /// The server refused the connection.
+    ConnectionRejected(ConnectFailedReason),
+    /// The operation needs an established session.
+    NotConnected,
+    /// The server has no session for this address.
+    UnknownConnection(SocketAddr),
+    /// An ordering channel outside the 32 RakNet provides was requested.
+    InvalidChannel(u8),
+    /// The configured MTU limits are out of order or below `MIN_MTU_SIZE`.
+    InvalidMtuLimits { min: u16, max: u16 },
+    /// An MTU size to probe falls outside the configured limits.
+    InvalidMtuSize(u16),
+}
+
+impl fmt::Display for RakError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "I/O error: {}", e),
+            Self::Decode(e) => write!(f, "Decode error: {}", e),
+            Self::UnexpectedPacket(id) => write!(f, "Unexpected packet: {}", id),
+            Self::Timeout(stage) => write!(f, "Timed out: {}", stage.as_str()),
+            Self::ConnectionRejected(reason) => write!(f, "Connection rejected: {}", reason.as_str()),
+            Self::NotConnected => write!(f, "Not connected"),
+            Self::UnknownConnection(address) => write!(f, "No connection for {}", address),
+            Self::InvalidChannel(channel) => write!(f, "Invalid ordering channel: {}", channel),
+            Self::InvalidMtuLimits { min, max } => write!(f, "Invalid MTU limits: {}..={}", min, max),
+            Self::InvalidMtuSize(size) => write!(f, "MTU size {} is outside the configured limits", size),
+        }
+    }
+}
+
+impl std::error::Error for RakError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            Self::Decode(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for RakError {
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<ConnectFailedReason> for RakError {
+    fn from(reason: ConnectFailedReason) -> Self {
+        match reason {
+            ConnectFailedReason::Timeout(stage) => Self::Timeout(stage),
+            reason => Self::ConnectionRejected(reason),
+        }
+    }
+}
+
+impl From<BinaryError> for RakError {
+    fn from(e: BinaryError) -> Self {
+        Self::Decode(e)
+    }
+}
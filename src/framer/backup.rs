@@ -0,0 +1,21 @@
+use crate::proto::Frame;
+
+/// A sent frameset kept until the peer acknowledges its datagram.
+#[derive(Debug, Clone)]
+pub struct Backup {
+    pub frames: Vec<Frame>,
+    pub size: usize,
+    pub sent_at: u64,
+    pub retransmission_timeout: u64,
+}
+
+impl Backup {
+    pub fn new(frames: Vec<Frame>, size: usize, sent_at: u64, retransmission_timeout: u64) -> Self {
+        Self { frames, size, sent_at, retransmission_timeout }
+    }
+
+    /// Only strictly past the timeout: an ACK due exactly now may still be unread.
+    pub fn is_expired(&self, now: u64) -> bool {
+        now.saturating_sub(self.sent_at) > self.retransmission_timeout
+    }
+}
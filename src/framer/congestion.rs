@@ -0,0 +1,129 @@
+use crate::proto::{serial_gt, serial_lt, serial_next};
+
+/// Decides how many bytes of reliable framesets may be unacknowledged at once.
+///
+/// The framer asks for the `window` before releasing each datagram and reports
+/// what happened to the ones it sent, so a policy can grow or shrink it.
+pub trait CongestionControl: Send {
+    fn window(&self) -> usize;
+
+    fn set_mtu_size(&mut self, _mtu_size: u16) {}
+
+    fn on_send(&mut self, _sequence: u32, _size: usize) {}
+
+    fn on_ack(&mut self, _sequence: u32, _size: usize) {}
+
+    fn on_nack(&mut self, _sequence: u32) {}
+
+    fn on_timeout(&mut self, _sequence: u32) {}
+}
+
+/// RakNet's sliding window: slow start until the first loss, then congestion
+/// avoidance, halving the window on a NACK and collapsing it on a timeout.
+#[derive(Debug, Clone)]
+pub struct SlidingWindow {
+    pub mtu_size: usize,
+    pub congestion_window: f64,
+    pub slow_start_threshold: f64,
+    pub next_sequence: u32,
+    pub backoff_sequence: u32,
+}
+
+impl SlidingWindow {
+    pub fn new(mtu_size: u16) -> Self {
+        Self {
+            mtu_size: mtu_size as usize,
+            congestion_window: mtu_size as f64,
+            slow_start_threshold: 0.0,
+            next_sequence: 0,
+            backoff_sequence: 0,
+        }
+    }
+
+    pub fn is_slow_start(&self) -> bool {
+        self.slow_start_threshold == 0.0 || self.congestion_window <= self.slow_start_threshold
+    }
+
+    /// Only back off once per window: losses of datagrams sent before the
+    /// last backoff were already accounted for.
+    fn should_back_off(&mut self, sequence: u32) -> bool {
+        if serial_lt(sequence, self.backoff_sequence) {
+            return false;
+        }
+        self.backoff_sequence = self.next_sequence;
+        true
+    }
+}
+
+impl CongestionControl for SlidingWindow {
+    fn window(&self) -> usize {
+        self.congestion_window as usize
+    }
+
+    fn set_mtu_size(&mut self, mtu_size: u16) {
+        self.mtu_size = mtu_size as usize;
+        self.congestion_window = self.congestion_window.max(mtu_size as f64);
+    }
+
+    fn on_send(&mut self, sequence: u32, _size: usize) {
+        if !serial_gt(self.next_sequence, sequence) {
+            self.next_sequence = serial_next(sequence);
+        }
+    }
+
+    fn on_ack(&mut self, _sequence: u32, _size: usize) {
+        let mtu_size = self.mtu_size as f64;
+        if self.is_slow_start() {
+            self.congestion_window += mtu_size;
+            if self.slow_start_threshold != 0.0 && self.congestion_window > self.slow_start_threshold {
+                self.congestion_window = self.slow_start_threshold + mtu_size * mtu_size / self.congestion_window;
+            }
+        } else {
+            self.congestion_window += mtu_size * mtu_size / self.congestion_window;
+        }
+    }
+
+    fn on_nack(&mut self, sequence: u32) {
+        if self.should_back_off(sequence) {
+            let mtu_size = self.mtu_size as f64;
+            self.slow_start_threshold = (self.congestion_window / 2.0).max(mtu_size);
+            self.congestion_window = self.slow_start_threshold;
+        }
+    }
+
+    fn on_timeout(&mut self, sequence: u32) {
+        if self.should_back_off(sequence) {
+            let mtu_size = self.mtu_size as f64;
+            self.slow_start_threshold = (self.congestion_window / 2.0).max(mtu_size);
+            self.congestion_window = mtu_size;
+        }
+    }
+}
+
+/// A constant number of bytes in flight, regardless of loss.
+#[derive(Debug, Clone)]
+pub struct FixedWindow {
+    pub window: usize,
+}
+
+impl FixedWindow {
+    pub fn new(window: usize) -> Self {
+        Self { window }
+    }
+}
+
+impl CongestionControl for FixedWindow {
+    fn window(&self) -> usize {
+        self.window
+    }
+}
+
+/// No limit: everything queued is sent on the next flush.
+#[derive(Debug, Clone, Default)]
+pub struct Unlimited;
+
+impl CongestionControl for Unlimited {
+    fn window(&self) -> usize {
+        usize::MAX
+    }
+}
@@ -0,0 +1,608 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use crate::proto::{ 
+    serial_add, serial_distance, serial_gt, serial_lt, serial_next, Ack, Frame, FrameSet, Nack, Priority,
+    Reliability, U24_MASK
+};
+use crate::error::RakError;
+
+use super::{
+    Backup, CongestionControl, FramerStats, Latency, Receipt, ReceiptRecord, ReliableWindow, SendQueue, SlidingWindow,
+    SplitAssembly, SplitDrop, SplitDropReason, SplitLimits
+};
+
+/// The smallest MTU RakNet will negotiate; it leaves room for the datagram and
+/// frame headers with space to spare for a payload.
+pub const MIN_MTU_SIZE: u16 = 400;
+pub const INITIAL_RETRANSMISSION_TIMEOUT: u64 = 1000;
+pub const MIN_RETRANSMISSION_TIMEOUT: u64 = 200;
+/// RFC 6298's clock granularity term: the peer only ACKs on its tick, so a
+/// datagram can wait this long for its ACK even on a perfectly steady link.
+pub const RETRANSMISSION_GRANULARITY: u64 = 100;
+pub const MAX_RETRANSMISSION_TIMEOUT: u64 = 10000;
+pub const ORDERING_CHANNELS: usize = 32;
+/// A jump in datagram sequence larger than this is only NACKed this far.
+pub const MAX_NACKED_GAP: u32 = 1024;
+
+/// The reliability layer of a RakNet session, with no I/O of its own.
+///
+/// Datagrams are fed in through `receive`, and the frames they carry come
+/// back out of `poll_message` once split, sequencing and ordering rules are
+/// satisfied. Anything the framer wants on the wire (framesets, ACKs and
+/// NACKs) is queued and handed out by `poll_datagram`, so the caller decides
+/// how and where it is sent.
+pub struct Framer {
+    pub mtu_size: u16,
+    pub current_time: u64,
+    pub datagram_queue: VecDeque<Vec<u8>>,
+    pub message_queue: VecDeque<Frame>,
+    pub last_input_sequence: Option<u32>,
+    pub received_frame_sequences: HashSet<u32>,
+    pub lost_frame_sequences: HashSet<u32>,
+    pub input_reliable_window: ReliableWindow,
+    pub input_datagram_window: ReliableWindow,
+    pub input_highest_sequence_index: [u32; ORDERING_CHANNELS],
+    pub input_order_index: [u32; ORDERING_CHANNELS],
+    pub input_ordering_queue: HashMap<u32, HashMap<u32, Frame>>,
+    pub fragments_queue: HashMap<u16, SplitAssembly>,
+    pub split_limits: SplitLimits,
+    pub split_drops: VecDeque<SplitDrop>,
+
+    pub output_sequence_index: [u32; ORDERING_CHANNELS],
+    pub output_order_index: [u32; ORDERING_CHANNELS],
+    /// Frames packed for the next datagram, in the order they were queued.
+    pub output_frames: VecDeque<Frame>,
+    pub output_frames_size: usize,
+    pub output_queue: SendQueue,
+    pub output_backup: HashMap<u32, Backup>,
+    pub bytes_in_flight: usize,
+    pub congestion: Box<dyn CongestionControl>,
+    /// Round trip time measured from ACKs; drives the retransmission timeout.
+    pub rtt: Latency,
+    pub stats: FramerStats,
+    pub output_sequence: u32,
+    pub output_split_index: u32,
+    pub output_reliable_index: u32,
+    pub output_receipt: u32,
+    /// Frames still unacknowledged per receipt number.
+    pub pending_receipts: HashMap<u32, usize>,
+    pub datagram_receipts: HashMap<u32, ReceiptRecord>,
+    pub receipt_queue: VecDeque<Receipt>,
+}
+
+
+impl Framer {
+    pub fn new(mtu_size: u16) -> Self {
+        Self { 
+            mtu_size, 
+            current_time: 0,
+            datagram_queue: VecDeque::new(),
+            message_queue: VecDeque::new(),
+            last_input_sequence: None,
+            received_frame_sequences: HashSet::new(),
+            lost_frame_sequences: HashSet::new(),
+            input_reliable_window: ReliableWindow::new(),
+            input_datagram_window: ReliableWindow::new(),
+            input_highest_sequence_index: [0; ORDERING_CHANNELS],
+            input_order_index: [0; ORDERING_CHANNELS],
+            input_ordering_queue: HashMap::new(),
+            fragments_queue: HashMap::new(),
+            split_limits: SplitLimits::default(),
+            split_drops: VecDeque::new(),
+
+            output_sequence_index: [0; ORDERING_CHANNELS],
+            output_order_index: [0; ORDERING_CHANNELS],
+            output_frames: VecDeque::new(),
+            output_frames_size: 0,
+            output_queue: SendQueue::new(),
+            output_backup: HashMap::new(),
+            bytes_in_flight: 0,
+            congestion: Box::new(SlidingWindow::new(mtu_size)),
+            rtt: Latency::default(),
+            stats: FramerStats::default(),
+            output_sequence: 0,
+            output_split_index: 0,
+            output_reliable_index: 0,
+            output_receipt: 0,
+            pending_receipts: HashMap::new(),
+            datagram_receipts: HashMap::new(),
+            receipt_queue: VecDeque::new(),
+        }
+    }
+
+    pub fn set_mtu_size(&mut self, mtu_size: u16) {
+        self.mtu_size = mtu_size;
+        self.congestion.set_mtu_size(mtu_size);
+    }
+
+    /// Whether anything is still waiting to be sent or acknowledged.
+    pub fn has_pending(&self) -> bool {
+        !self.output_queue.is_empty() || !self.output_backup.is_empty()
+    }
+
+    pub fn receive(&mut self, datagram: &[u8], now: u64) -> Result<(), RakError> {
+        self.current_time = now;
+        if datagram.is_empty() {
+            return Ok(());
+        }
+
+        match datagram[0] {
+            Ack::ID => {
+                let ack = Ack::deserialize(datagram)?;
+                self.on_ack(&ack);
+            }
+            Nack::ID => {
+                let nack = Nack::deserialize(datagram)?;
+                self.on_nack(&nack);
+            }
+            packet_id if (packet_id & 0xf0) == FrameSet::ID => {
+                let frameset = FrameSet::deserialize(datagram)?;
+                self.on_frameset(&frameset);
+            }
+            packet_id => return Err(RakError::UnexpectedPacket(packet_id))
+        }
+        Ok(())
+    }
+
+    pub fn poll_datagram(&mut self) -> Option<Vec<u8>> {
+        self.datagram_queue.pop_front()
+    }
+
+    pub fn poll_message(&mut self) -> Option<Frame> {
+        self.message_queue.pop_front()
+    }
+
+    pub fn poll_receipt(&mut self) -> Option<Receipt> {
+        self.receipt_queue.pop_front()
+    }
+
+    pub fn poll_split_drop(&mut self) -> Option<SplitDrop> {
+        self.split_drops.pop_front()
+    }
+
+    pub fn on_ack(&mut self, ack: &Ack) {
+        self.stats.acks_received += 1;
+        for sequence in &ack.sequences {
+            if let Some(backup) = self.take_backup(*sequence) {
+                self.congestion.on_ack(*sequence, backup.size);
+                // Resent frames go out under a new sequence number, so every sample is unambiguous.
+                self.rtt.update(self.current_time.saturating_sub(backup.sent_at));
+            }
+            if let Some(record) = self.datagram_receipts.remove(sequence) {
+                for (receipt, _) in record.receipts {
+                    self.acknowledge_receipt(receipt);
+                }
+            }
+        }
+    }
+
+    fn acknowledge_receipt(&mut self, receipt: u32) {
+        if let Some(pending) = self.pending_receipts.get_mut(&receipt) {
+            *pending -= 1;
+            if *pending == 0 {
+                self.pending_receipts.remove(&receipt);
+                self.receipt_queue.push_back(Receipt::Acked(receipt));
+            }
+        }
+    }
+
+    /// Reliable frames are resent under a new datagram that carries the receipt
+    /// again, so only unreliable ones are reported lost.
+    fn drop_receipts(&mut self, record: ReceiptRecord) {
+        for (receipt, reliable) in record.receipts {
+            if !reliable && self.pending_receipts.remove(&receipt).is_some() {
+                self.receipt_queue.push_back(Receipt::Lost(receipt));
+            }
+        }
+    }
+
+    pub fn on_nack(&mut self, nack: &Nack) {
+        self.stats.nacks_received += 1;
+        for sequence in &nack.sequences {
+            if let Some(backup) = self.take_backup(*sequence) {
+                self.congestion.on_nack(*sequence);
+                self.resend(backup);
+            }
+            if let Some(record) = self.datagram_receipts.remove(sequence) {
+                self.drop_receipts(record);
+            }
+        }
+        self.flush();
+    }
+
+    fn take_backup(&mut self, sequence: u32) -> Option<Backup> {
+        let backup = self.output_backup.remove(&sequence)?;
+        self.bytes_in_flight = self.bytes_in_flight.saturating_sub(backup.size);
+        Some(backup)
+    }
+
+    /// How long to wait for an ACK before resending, from the measured round trip
+    /// (RFC 6298: smoothed RTT plus the larger of the granularity and four times
+    /// the variance).
+    pub fn retransmission_timeout(&self) -> u64 {
+        if self.rtt.samples == 0 {
+            return INITIAL_RETRANSMISSION_TIMEOUT;
+        }
+        let margin = (4.0 * self.rtt.rtt_variance).max(RETRANSMISSION_GRANULARITY as f64);
+        let timeout = (self.rtt.rtt + margin).ceil() as u64;
+        timeout.clamp(MIN_RETRANSMISSION_TIMEOUT, MAX_RETRANSMISSION_TIMEOUT)
+    }
+
+    pub fn resend_expired(&mut self) {
+        let mut expired: Vec<u32> = self.output_backup.iter()
+            .filter(|(_, backup)| backup.is_expired(self.current_time))
+            .map(|(sequence, _)| *sequence)
+            .collect();
+        expired.sort();
+
+        for sequence in expired {
+            if let Some(backup) = self.take_backup(sequence) {
+                self.congestion.on_timeout(sequence);
+                self.resend(backup);
+            }
+        }
+
+        let mut expired: Vec<u32> = self.datagram_receipts.iter()
+            .filter(|(_, record)| record.is_expired(self.current_time))
+            .map(|(sequence, _)| *sequence)
+            .collect();
+        expired.sort();
+
+        for sequence in expired {
+            if let Some(record) = self.datagram_receipts.remove(&sequence) {
+                self.drop_receipts(record);
+            }
+        }
+    }
+
+    fn resend(&mut self, backup: Backup) {
+        self.stats.datagrams_resent += 1;
+        self.stats.frames_resent += backup.frames.len() as u64;
+        // Lost frames go back to the front of the queue, ahead of anything newer.
+        for frame in backup.frames.into_iter().rev() {
+            self.output_queue.push_front(frame, Priority::Immediate);
+        }
+    }
+
+    pub fn tick(&mut self, now: u64) {
+        self.current_time = now;
+        let sequences: Vec<u32> = self.received_frame_sequences.clone()
+            .into_iter()
+            .collect();
+        
+        if !sequences.is_empty() {
+            self.received_frame_sequences.clear();
+            
+            let ack = Ack::new(sequences);
+            self.datagram_queue.push_back(ack.serialize());
+        }
+
+        let nack_sequences: Vec<u32> = self.lost_frame_sequences.clone()
+            .into_iter()
+            .collect();
+        
+        if !nack_sequences.is_empty() {
+            self.lost_frame_sequences.clear();
+            
+            let nack = Nack::new(nack_sequences);
+            self.datagram_queue.push_back(nack.serialize());
+        }
+
+        self.resend_expired();
+        self.expire_splits();
+        self.flush();
+    }
+
+    pub fn on_frameset(&mut self, frameset: &FrameSet) {
+        let sequence = frameset.sequence & U24_MASK;
+        self.lost_frame_sequences.remove(&sequence);
+        self.received_frame_sequences.insert(sequence);
+        // A repeated datagram is ACKed again but its frames were already handled.
+        if !self.input_datagram_window.receive_sliding(sequence) {
+            return;
+        }
+
+        // A late datagram is still ACKed and read; any reliable frame it repeats
+        // is caught by the reliable window below.
+        let expected = self.last_input_sequence.map_or(0, serial_next);
+        if !serial_lt(sequence, expected) {
+            let gap = serial_distance(expected, sequence).min(MAX_NACKED_GAP);
+            for offset in 0..gap {
+                let missing = serial_add(expected, offset);
+                if !self.received_frame_sequences.contains(&missing) {
+                    self.lost_frame_sequences.insert(missing);
+                }
+            }
+            self.last_input_sequence = Some(sequence);
+        }
+
+        for frame in &frameset.frames {
+            if let (true, Some(index)) = (frame.reliability.is_reliable(), frame.reliable_frame_index) {
+                if !self.input_reliable_window.receive(index) {
+                    continue;
+                }
+            }
+            self.handle_frame(frame);
+        }
+    }
+
+    pub fn handle_frame(&mut self, frame: &Frame) {
+        if frame.is_split() {
+            self.handle_split_frame(frame);
+        } else if frame.reliability.is_sequenced() {
+            self.handle_sequenced_frame(frame);
+        } else if frame.reliability.is_ordered() {
+            self.handle_ordered_frame(frame);
+        } else {
+            self.message_queue.push_back(frame.clone());
+        }
+    }
+
+    pub fn handle_split_frame(&mut self, frame: &Frame) {
+        let (Some(split_id), Some(split_index), Some(split_size)) =
+            (frame.split_id, frame.split_frame_index, frame.split_size) else { return };
+        let limits = self.split_limits;
+
+        if split_size > limits.max_fragments {
+            return self.drop_split(split_id, SplitDropReason::TooManyFragments);
+        }
+        if split_index >= split_size {
+            return self.drop_split(split_id, SplitDropReason::Inconsistent);
+        }
+        if !self.fragments_queue.contains_key(&split_id) && self.fragments_queue.len() >= limits.max_concurrent_splits {
+            return self.drop_split(split_id, SplitDropReason::TooManySplits);
+        }
+
+        let assembly = self.fragments_queue.entry(split_id)
+            .or_insert_with(|| SplitAssembly::new(split_size, self.current_time));
+        if assembly.split_size != split_size {
+            return self.drop_split(split_id, SplitDropReason::Inconsistent);
+        }
+        if assembly.bytes + frame.payload.len() > limits.max_reassembled_bytes {
+            return self.drop_split(split_id, SplitDropReason::TooLarge);
+        }
+        assembly.insert(split_index, frame.clone(), self.current_time);
+
+        if !assembly.is_complete() {
+            return;
+        }
+        let Some(assembly) = self.fragments_queue.remove(&split_id) else { return };
+
+        let mut reassembled_frame = Frame::new();
+        reassembled_frame.reliability = frame.reliability;
+        reassembled_frame.reliable_frame_index = assembly.fragments.get(&0)
+            .and_then(|f| f.reliable_frame_index);
+        reassembled_frame.sequence_frame_index = frame.sequence_frame_index;
+        reassembled_frame.ordered_frame_index = frame.ordered_frame_index;
+        reassembled_frame.order_channel = frame.order_channel;
+        reassembled_frame.payload = assembly.payload();
+
+        self.handle_frame(&reassembled_frame);
+    }
+
+    fn drop_split(&mut self, split_id: u16, reason: SplitDropReason) {
+        self.fragments_queue.remove(&split_id);
+        self.split_drops.push_back(SplitDrop { split_id, reason });
+    }
+
+    /// Gives up on partial split packets that have stopped receiving fragments.
+    pub fn expire_splits(&mut self) {
+        let timeout = self.split_limits.timeout;
+        let mut expired: Vec<u16> = self.fragments_queue.iter()
+            .filter(|(_, assembly)| assembly.is_expired(self.current_time, timeout))
+            .map(|(split_id, _)| *split_id)
+            .collect();
+        expired.sort();
+
+        for split_id in expired {
+            self.drop_split(split_id, SplitDropReason::TimedOut);
+        }
+    }
+
+    pub fn handle_sequenced_frame(&mut self, frame: &Frame) {
+        let channel = frame.order_channel.unwrap_or(0) as usize;
+        if channel >= ORDERING_CHANNELS {
+            return;
+        }
+        let ordered_frame_index = frame.ordered_frame_index.unwrap_or(0);
+        let sequence_frame_index = frame.sequence_frame_index.unwrap_or(0);
+
+        // Anything sent before the last ordered frame on the channel is stale.
+        if serial_lt(ordered_frame_index, self.input_order_index[channel]) {
+            return;
+        }
+        if !serial_lt(sequence_frame_index, self.input_highest_sequence_index[channel]) {
+            self.input_highest_sequence_index[channel] = serial_next(sequence_frame_index);
+            self.message_queue.push_back(frame.clone());
+        }
+    }
+
+    pub fn handle_ordered_frame(&mut self, frame: &Frame) {
+        let channel = frame.order_channel.unwrap_or(0) as usize;
+        if channel >= ORDERING_CHANNELS {
+            return;
+        }
+        let ordered_frame_index = frame.ordered_frame_index.unwrap_or(0);
+        let expected_order_index = self.input_order_index[channel];
+
+        if ordered_frame_index == expected_order_index {
+            self.message_queue.push_back(frame.clone());
+            self.input_highest_sequence_index[channel] = 0;
+
+            // Drain everything that was waiting on this frame. The queue can only
+            // hold what the reliable window let through, so this always ends.
+            let mut next_order_index = serial_next(expected_order_index);
+            if let Some(out_of_order_queue) = self.input_ordering_queue.get_mut(&(channel as u32)) {
+                while let Some(next_frame) = out_of_order_queue.remove(&next_order_index) {
+                    self.message_queue.push_back(next_frame);
+                    next_order_index = serial_next(next_order_index);
+                }
+            }
+            self.input_order_index[channel] = next_order_index;
+        } else if serial_gt(ordered_frame_index, expected_order_index) {
+            self.input_ordering_queue.entry(channel as u32)
+                .or_default()
+                .insert(ordered_frame_index, frame.clone());
+        }
+    }
+
+
+    pub fn pls_frame(&mut self, payload: Vec<u8>) -> Frame {
+        let mut frame = Frame::new();
+        frame.reliability = Reliability::ReliableOrdered;
+        frame.order_channel = Some(0);
+        frame.payload = payload;
+        frame
+    }
+
+    /// Assigns reliable, sequence and order indices and splits the payload if it
+    /// does not fit in one datagram, then queues the result at `priority`.
+    ///
+    /// Returns the receipt number; for the ack-receipt reliabilities it is
+    /// reported back through `poll_receipt`.
+    pub fn send_frame(&mut self, frame: &mut Frame, priority: Option<Priority>) -> Result<u32, RakError> {
+        let priority = priority.unwrap_or_default();
+
+        if frame.reliability.is_sequenced() || frame.reliability.is_ordered() {
+            let order_channel = frame.order_channel.unwrap_or(0);
+            if order_channel as usize >= ORDERING_CHANNELS {
+                return Err(RakError::InvalidChannel(order_channel));
+            }
+            let channel = order_channel as usize;
+            frame.order_channel = Some(order_channel);
+            frame.ordered_frame_index = Some(self.output_order_index[channel]);
+            if frame.reliability.is_sequenced() {
+                frame.sequence_frame_index = Some(self.output_sequence_index[channel]);
+                self.output_sequence_index[channel] = serial_next(self.output_sequence_index[channel]);
+            } else {
+                self.output_order_index[channel] = serial_next(self.output_order_index[channel]);
+                self.output_sequence_index[channel] = 0;
+            }
+        } else {
+            frame.order_channel = None;
+        }
+
+        let receipt = self.output_receipt;
+        self.output_receipt = self.output_receipt.wrapping_add(1);
+        frame.receipt = if frame.reliability.is_ack_receipt() { Some(receipt) } else { None };
+
+        let max_mtu = (self.mtu_size - 36) as usize;
+
+        if frame.payload.len() > max_mtu {
+            // Fragments must all arrive for the packet to be rebuilt, so split
+            // packets are always sent reliably, as RakNet does.
+            let reliability = match frame.reliability {
+                Reliability::Unreliable => Reliability::Reliable,
+                Reliability::UnreliableSequenced => Reliability::ReliableSequenced,
+                Reliability::UnreliableWithAckReceipt => Reliability::ReliableWithAckReceipt,
+                reliability => reliability,
+            };
+            self.output_split_index += 1;
+            let split_id = (self.output_split_index % 65_536) as u16;
+            let split_size = frame.payload.len().div_ceil(max_mtu) as u32;
+            if let Some(receipt) = frame.receipt {
+                self.pending_receipts.insert(receipt, split_size as usize);
+            }
+
+            for (split_index, chunk) in frame.payload.chunks(max_mtu).enumerate() {
+                let mut split_frame = Frame::new();
+                split_frame.reliability = reliability;
+                split_frame.reliable_frame_index = Some(self.next_reliable_index());
+                split_frame.sequence_frame_index = frame.sequence_frame_index;
+                split_frame.ordered_frame_index = frame.ordered_frame_index;
+                split_frame.order_channel = frame.order_channel;
+                split_frame.split_id = Some(split_id);
+                split_frame.split_frame_index = Some(split_index as u32);
+                split_frame.split_size = Some(split_size);
+                split_frame.payload = chunk.to_vec();
+                split_frame.receipt = frame.receipt;
+
+                self.queue_frame(&split_frame, Some(priority));
+            }
+        } else {
+            frame.reliable_frame_index = if frame.reliability.is_reliable() {
+                Some(self.next_reliable_index())
+            } else {
+                None
+            };
+            if let Some(receipt) = frame.receipt {
+                self.pending_receipts.insert(receipt, 1);
+            }
+            self.queue_frame(frame, Some(priority));
+        }
+        Ok(receipt)
+    }
+
+    fn next_reliable_index(&mut self) -> u32 {
+        let index = self.output_reliable_index;
+        self.output_reliable_index = serial_next(self.output_reliable_index);
+        index
+    }
+
+    pub fn queue_frame(&mut self, frame: &Frame, priority: Option<Priority>) {
+        let priority = priority.unwrap_or_default();
+        self.output_queue.push(frame.clone(), priority);
+
+        if priority == Priority::Immediate {
+            self.flush();
+        }
+    }
+
+    /// Packs buffered frames into datagrams for as long as the congestion window allows.
+    pub fn flush(&mut self) {
+        let max_size = (self.mtu_size - 36) as usize;
+        while !self.output_queue.is_empty() && self.can_send() {
+            while let Some(frame) = self.output_queue.front() {
+                if !self.output_frames.is_empty() && 4 + self.output_frames_size + frame.get_size() > max_size {
+                    break;
+                }
+                if let Some(frame) = self.output_queue.pop() {
+                    self.output_frames_size += frame.get_size();
+                    self.output_frames.push_back(frame);
+                }
+            }
+            self.send_queue(self.output_frames.len());
+        }
+    }
+
+    /// One more datagram fits in the window; with nothing in flight one is always allowed.
+    fn can_send(&self) -> bool {
+        self.bytes_in_flight == 0
+            || self.bytes_in_flight.saturating_add(self.mtu_size as usize) <= self.congestion.window()
+    }
+
+    pub fn send_queue(&mut self, size: usize) {
+        if self.output_frames.is_empty() { return; }
+        
+        let frames: Vec<Frame> = self.output_frames
+            .drain(..size.min(self.output_frames.len()))
+            .collect();
+        let packed_size: usize = frames.iter().map(|frame| frame.get_size()).sum();
+        self.output_frames_size -= packed_size;
+        
+        let sequence = self.output_sequence;
+        self.output_sequence = serial_next(self.output_sequence);
+        let frameset = FrameSet::new(sequence, frames);
+        let reliable_frames: Vec<Frame> = frameset.frames.iter()
+            .filter(|frame| frame.reliability.is_reliable())
+            .cloned()
+            .collect();
+        let datagram = frameset.serialize();
+        if !reliable_frames.is_empty() {
+            let backup = Backup::new(reliable_frames, datagram.len(), self.current_time, self.retransmission_timeout());
+            self.output_backup.insert(sequence, backup);
+            self.bytes_in_flight += datagram.len();
+        }
+        let receipts: Vec<(u32, bool)> = frameset.frames.iter()
+            .filter_map(|frame| frame.receipt.map(|receipt| (receipt, frame.reliability.is_reliable())))
+            .collect();
+        if !receipts.is_empty() {
+            let record = ReceiptRecord::new(receipts, self.current_time, self.retransmission_timeout());
+            self.datagram_receipts.insert(sequence, record);
+        }
+        self.congestion.on_send(sequence, datagram.len());
+        self.stats.datagrams_sent += 1;
+        
+        self.datagram_queue.push_back(datagram);
+    }
+}
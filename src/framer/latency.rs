@@ -0,0 +1,29 @@
+/// A smoothed round trip estimate, computed the same way TCP does it (RFC 6298).
+/// All values are in milliseconds.
+///
+/// The client feeds one from `ConnectedPing` / `ConnectedPong` exchanges to report
+/// latency; each `Framer` keeps its own, fed from how long datagrams wait for
+/// their ACK, to pick the retransmission timeout. `last_ping` is the latest
+/// sample from whichever source.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Latency {
+    pub rtt: f64,
+    pub rtt_variance: f64,
+    pub last_ping: u64,
+    pub samples: u32,
+}
+
+impl Latency {
+    pub fn update(&mut self, sample: u64) {
+        let sample_f = sample as f64;
+        if self.samples == 0 {
+            self.rtt = sample_f;
+            self.rtt_variance = sample_f / 2.0;
+        } else {
+            self.rtt_variance = 0.75 * self.rtt_variance + 0.25 * (self.rtt - sample_f).abs();
+            self.rtt = 0.875 * self.rtt + 0.125 * sample_f;
+        }
+        self.last_ping = sample;
+        self.samples = self.samples.saturating_add(1);
+    }
+}
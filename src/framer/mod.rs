@@ -0,0 +1,20 @@
+#[allow(clippy::module_inception)]
+mod framer;
+mod backup;
+mod congestion;
+mod latency;
+mod receipt;
+mod reliable_window;
+mod send_queue;
+mod split;
+mod stats;
+
+pub use framer::*;
+pub use backup::*;
+pub use congestion::*;
+pub use latency::*;
+pub use receipt::*;
+pub use reliable_window::*;
+pub use send_queue::*;
+pub use split::*;
+pub use stats::*;
@@ -0,0 +1,26 @@
+/// What became of a frame sent with one of the ack-receipt reliabilities.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Receipt {
+    Acked(u32),
+    Lost(u32),
+}
+
+/// The receipts carried by one sent datagram, kept until it is ACKed, NACKed
+/// or times out.
+#[derive(Debug, Clone)]
+pub struct ReceiptRecord {
+    /// Receipt number and whether the frame carrying it is reliable.
+    pub receipts: Vec<(u32, bool)>,
+    pub sent_at: u64,
+    pub timeout: u64,
+}
+
+impl ReceiptRecord {
+    pub fn new(receipts: Vec<(u32, bool)>, sent_at: u64, timeout: u64) -> Self {
+        Self { receipts, sent_at, timeout }
+    }
+
+    pub fn is_expired(&self, now: u64) -> bool {
+        now.saturating_sub(self.sent_at) > self.timeout
+    }
+}
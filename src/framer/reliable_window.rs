@@ -0,0 +1,60 @@
+use std::collections::HashSet;
+
+use crate::proto::{serial_distance, serial_lt, serial_next, U24_MASK};
+
+/// How far past the oldest missing index we are willing to remember.
+pub const RELIABLE_WINDOW_SIZE: u32 = 65536;
+
+/// Remembers which reliable frame indices have arrived so that a frame resent
+/// in a new datagram is only delivered once.
+///
+/// Everything below `base` has been received; `received` holds the indices
+/// that arrived past the first gap. Indices are 24-bit and wrap.
+#[derive(Debug, Clone, Default)]
+pub struct ReliableWindow {
+    pub base: u32,
+    pub received: HashSet<u32>,
+}
+
+impl ReliableWindow {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records `index` and returns whether it is new. Indices behind the window
+    /// are duplicates; ones too far ahead of it are refused.
+    pub fn receive(&mut self, index: u32) -> bool {
+        let index = index & U24_MASK;
+        if serial_lt(index, self.base) || serial_distance(self.base, index) >= RELIABLE_WINDOW_SIZE {
+            return false;
+        }
+
+        if index == self.base {
+            self.base = serial_next(self.base);
+            while self.received.remove(&self.base) {
+                self.base = serial_next(self.base);
+            }
+            true
+        } else {
+            self.received.insert(index)
+        }
+    }
+
+    /// Like `receive`, but an index too far ahead moves the window up to it
+    /// instead of being refused, forgetting the gaps it leaves behind.
+    ///
+    /// Datagram sequence numbers need this: a lost datagram is never resent
+    /// under the same number, so its gap would otherwise hold the window forever.
+    pub fn receive_sliding(&mut self, index: u32) -> bool {
+        let index = index & U24_MASK;
+        if !serial_lt(index, self.base) && serial_distance(self.base, index) >= RELIABLE_WINDOW_SIZE {
+            self.base = index.wrapping_sub(RELIABLE_WINDOW_SIZE - 1) & U24_MASK;
+            let base = self.base;
+            self.received.retain(|&received| !serial_lt(received, base));
+            while self.received.remove(&self.base) {
+                self.base = serial_next(self.base);
+            }
+        }
+        self.receive(index)
+    }
+}
@@ -0,0 +1,80 @@
+use std::collections::VecDeque;
+
+use crate::proto::{Frame, Priority};
+
+const LEVELS: [Priority; Priority::COUNT] = [Priority::Immediate, Priority::High, Priority::Medium, Priority::Low];
+
+/// Frames waiting to go into a datagram, one FIFO per priority level.
+///
+/// Levels are drained by weight rather than strictly: each pop advances the
+/// level's weight by `Priority::weight_step`, and the level with the lowest
+/// weight goes next.
+#[derive(Debug, Clone)]
+pub struct SendQueue {
+    pub queues: [VecDeque<Frame>; Priority::COUNT],
+    pub next_weights: [u64; Priority::COUNT],
+}
+
+impl Default for SendQueue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SendQueue {
+    pub fn new() -> Self {
+        Self {
+            queues: Default::default(),
+            next_weights: LEVELS.map(|priority| priority.initial_weight()),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queues.iter().all(|queue| queue.is_empty())
+    }
+
+    pub fn len(&self) -> usize {
+        self.queues.iter().map(|queue| queue.len()).sum()
+    }
+
+    pub fn push(&mut self, frame: Frame, priority: Priority) {
+        self.activate(priority as usize);
+        self.queues[priority as usize].push_back(frame);
+    }
+
+    /// Puts a frame back ahead of everything else at its level.
+    pub fn push_front(&mut self, frame: Frame, priority: Priority) {
+        self.activate(priority as usize);
+        self.queues[priority as usize].push_front(frame);
+    }
+
+    pub fn front(&self) -> Option<&Frame> {
+        self.next_level().and_then(|level| self.queues[level].front())
+    }
+
+    pub fn pop(&mut self) -> Option<Frame> {
+        let level = self.next_level()?;
+        self.next_weights[level] += LEVELS[level].weight_step();
+        self.queues[level].pop_front()
+    }
+
+    fn activate(&mut self, level: usize) {
+        if self.is_empty() {
+            self.next_weights = LEVELS.map(|priority| priority.initial_weight());
+        } else if self.queues[level].is_empty() {
+            // A level that sat idle does not get to spend the credit it built up.
+            let floor = self.lowest_weight().unwrap_or(0);
+            self.next_weights[level] = self.next_weights[level].max(floor);
+        }
+    }
+
+    fn next_level(&self) -> Option<usize> {
+        (0..Priority::COUNT)
+            .filter(|level| !self.queues[*level].is_empty())
+            .min_by_key(|level| (self.next_weights[*level], *level))
+    }
+
+    fn lowest_weight(&self) -> Option<u64> {
+        self.next_level().map(|level| self.next_weights[level])
+    }
+}
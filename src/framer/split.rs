@@ -0,0 +1,94 @@
+use std::collections::HashMap;
+
+use crate::proto::Frame;
+
+/// Caps on what a peer can make us buffer while reassembling split packets.
+#[derive(Debug, Clone, Copy)]
+pub struct SplitLimits {
+    pub max_fragments: u32,
+    pub max_reassembled_bytes: usize,
+    pub max_concurrent_splits: usize,
+    /// Milliseconds without a new fragment before a partial packet is dropped.
+    pub timeout: u64,
+}
+
+impl Default for SplitLimits {
+    fn default() -> Self {
+        Self {
+            max_fragments: 8192,
+            max_reassembled_bytes: 8 * 1024 * 1024,
+            max_concurrent_splits: 16,
+            timeout: 30000,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SplitDropReason {
+    TooManyFragments,
+    TooLarge,
+    TooManySplits,
+    Inconsistent,
+    TimedOut,
+}
+
+impl SplitDropReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::TooManyFragments => "too_many_fragments",
+            Self::TooLarge => "too_large",
+            Self::TooManySplits => "too_many_splits",
+            Self::Inconsistent => "inconsistent",
+            Self::TimedOut => "timed_out",
+        }
+    }
+}
+
+/// A partial split packet that was given up on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SplitDrop {
+    pub split_id: u16,
+    pub reason: SplitDropReason,
+}
+
+/// The fragments received so far for one split ID.
+#[derive(Debug, Clone)]
+pub struct SplitAssembly {
+    pub split_size: u32,
+    pub fragments: HashMap<u32, Frame>,
+    pub bytes: usize,
+    pub updated_at: u64,
+}
+
+impl SplitAssembly {
+    pub fn new(split_size: u32, now: u64) -> Self {
+        Self { split_size, fragments: HashMap::new(), bytes: 0, updated_at: now }
+    }
+
+    pub fn insert(&mut self, index: u32, frame: Frame, now: u64) {
+        self.updated_at = now;
+        if !self.fragments.contains_key(&index) {
+            self.bytes += frame.payload.len();
+            self.fragments.insert(index, frame);
+        }
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.fragments.len() == self.split_size as usize
+    }
+
+    pub fn is_expired(&self, now: u64, timeout: u64) -> bool {
+        now.saturating_sub(self.updated_at) >= timeout
+    }
+
+    /// Concatenates the payloads in fragment order; only meaningful once complete.
+    pub fn payload(&self) -> Vec<u8> {
+        let mut payload = Vec::with_capacity(self.bytes);
+        for index in 0..self.split_size {
+            if let Some(fragment) = self.fragments.get(&index) {
+                payload.extend_from_slice(&fragment.payload);
+            }
+        }
+        payload
+    }
+}
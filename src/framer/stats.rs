@@ -0,0 +1,8 @@
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FramerStats {
+    pub datagrams_sent: u64,
+    pub datagrams_resent: u64,
+    pub frames_resent: u64,
+    pub acks_received: u64,
+    pub nacks_received: u64,
+}
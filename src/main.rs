@@ -1,13 +1,5 @@
-mod binary_stream;
-
-pub use binary_stream::*;
-mod socket;
 use chrono::Utc;
-pub use socket::*;
-mod proto;
-pub use proto::*;
-mod client;
-pub use client::*;
+use sanctumterra_raknet::*;
 
 // pub const MAGIC: [u8; 16] = [
 //     0x00, 0xFF, 0xFF, 0x00,
@@ -18,12 +10,12 @@ pub use client::*;
 
 
 fn main() {
-    let mut client = Client::new("135.148.137.229".to_string(), 19132);
+    let mut client = Client::new("135.148.137.229".to_string(), 19132).unwrap();
     println!("Connecting to server... {:?}", Utc::now().timestamp());
     client.connect().unwrap();
 
     loop {
-        client.tick();
+        client.tick().unwrap();
         let _data = client.receive().unwrap();
         // println!("{:?}", data);
     }
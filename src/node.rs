@@ -0,0 +1,304 @@
+use napi_derive::*;
+use napi::bindgen_prelude::*;
+
+use crate::{Client, ClientConfig, ClientEvent, ConnectFailedReason, Priority, RakError, Reliability};
+
+impl From<RakError> for Error {
+    fn from(e: RakError) -> Self {
+        let status = match e {
+            RakError::Decode(_)
+            | RakError::UnexpectedPacket(_)
+            | RakError::InvalidChannel(_)
+            | RakError::InvalidMtuLimits { .. }
+            | RakError::InvalidMtuSize(_) => Status::InvalidArg,
+            _ => Status::GenericFailure,
+        };
+        Error::new(status, e.to_string())
+    }
+}
+
+#[napi]
+pub enum PacketPriority {
+    Immediate,
+    High,
+    Medium,
+    Low
+}
+
+impl From<PacketPriority> for Priority {
+    fn from(priority: PacketPriority) -> Self {
+        match priority {
+            PacketPriority::Immediate => Priority::Immediate,
+            PacketPriority::High => Priority::High,
+            PacketPriority::Medium => Priority::Medium,
+            PacketPriority::Low => Priority::Low,
+        }
+    }
+}
+
+#[napi]
+pub enum PacketReliability {
+    Unreliable,
+    UnreliableSequenced,
+    Reliable,
+    ReliableOrdered,
+    ReliableSequenced,
+    UnreliableWithAckReceipt,
+    ReliableWithAckReceipt,
+    ReliableOrderedWithAckReceipt
+}
+
+impl From<PacketReliability> for Reliability {
+    fn from(reliability: PacketReliability) -> Self {
+        Reliability::from_u8(reliability as u8)
+    }
+}
+
+/// Constructor options; anything left out keeps the `ClientConfig` default.
+#[napi(object)]
+pub struct JsClientOptions {
+    pub guid: Option<BigInt>,
+    pub protocol: Option<u32>,
+    pub adopt_server_protocol: Option<bool>,
+    pub mtu_sizes: Option<Vec<u32>>,
+    pub mtu_attempts_per_size: Option<u32>,
+    pub min_mtu_size: Option<u32>,
+    pub max_mtu_size: Option<u32>,
+    pub bind_address: Option<String>,
+    pub handshake_retry_interval: Option<u32>,
+    pub handshake_max_retries: Option<u32>,
+    pub disconnect_timeout: Option<u32>,
+    pub session_timeout: Option<u32>,
+    pub ping_interval: Option<u32>,
+    pub max_split_fragments: Option<u32>,
+    pub max_split_bytes: Option<u32>,
+    pub max_concurrent_splits: Option<u32>,
+    pub split_timeout: Option<u32>,
+    pub magic: Option<Vec<u8>>,
+}
+
+fn invalid_option(name: &str) -> Error {
+    Error::new(Status::InvalidArg, format!("Invalid client option: {}", name))
+}
+
+impl TryFrom<JsClientOptions> for ClientConfig {
+    type Error = Error;
+
+    fn try_from(options: JsClientOptions) -> Result<Self> {
+        let mut config = ClientConfig::default();
+        if let Some(guid) = options.guid {
+            let (guid, lossless) = guid.get_i64();
+            if !lossless {
+                return Err(invalid_option("guid"));
+            }
+            config = config.guid(guid);
+        }
+        if let Some(protocol) = options.protocol {
+            config = config.protocol(u8::try_from(protocol).map_err(|_| invalid_option("protocol"))?);
+        }
+        if let Some(adopt) = options.adopt_server_protocol {
+            config = config.adopt_server_protocol(adopt);
+        }
+        if let Some(mtu_sizes) = options.mtu_sizes {
+            let mtu_sizes = mtu_sizes.into_iter()
+                .map(|size| u16::try_from(size).map_err(|_| invalid_option("mtuSizes")))
+                .collect::<Result<Vec<u16>>>()?;
+            if mtu_sizes.is_empty() {
+                return Err(invalid_option("mtuSizes"));
+            }
+            config = config.mtu_sizes(mtu_sizes);
+        }
+        if let Some(attempts) = options.mtu_attempts_per_size {
+            config = config.mtu_attempts_per_size(attempts);
+        }
+        if let Some(min_mtu_size) = options.min_mtu_size {
+            config.min_mtu_size = u16::try_from(min_mtu_size).map_err(|_| invalid_option("minMtuSize"))?;
+        }
+        if let Some(max_mtu_size) = options.max_mtu_size {
+            config.max_mtu_size = u16::try_from(max_mtu_size).map_err(|_| invalid_option("maxMtuSize"))?;
+        }
+        if let Some(bind_address) = options.bind_address {
+            config = config.bind_address(bind_address);
+        }
+        if let Some(interval) = options.handshake_retry_interval {
+            config = config.handshake_retry_interval(interval.into());
+        }
+        if let Some(retries) = options.handshake_max_retries {
+            config = config.handshake_max_retries(retries);
+        }
+        if let Some(timeout) = options.disconnect_timeout {
+            config = config.disconnect_timeout(timeout.into());
+        }
+        if let Some(timeout) = options.session_timeout {
+            config = config.session_timeout(timeout.into());
+        }
+        if let Some(interval) = options.ping_interval {
+            config = config.ping_interval(interval.into());
+        }
+        if let Some(fragments) = options.max_split_fragments {
+            config.split_limits.max_fragments = fragments;
+        }
+        if let Some(bytes) = options.max_split_bytes {
+            config.split_limits.max_reassembled_bytes = bytes as usize;
+        }
+        if let Some(splits) = options.max_concurrent_splits {
+            config.split_limits.max_concurrent_splits = splits as usize;
+        }
+        if let Some(timeout) = options.split_timeout {
+            config.split_limits.timeout = timeout.into();
+        }
+        if let Some(magic) = options.magic {
+            config = config.magic(magic.try_into().map_err(|_| invalid_option("magic"))?);
+        }
+        Ok(config)
+    }
+}
+
+#[napi(object)]
+pub struct JsLatency {
+    pub rtt: f64,
+    pub rtt_variance: f64,
+    pub last_ping: f64,
+}
+
+#[napi]
+pub struct RaknetClient {
+    client: Client
+}
+
+#[napi]
+impl RaknetClient {
+    #[napi(constructor)]
+    pub fn new(host: String, port: u32, options: Option<JsClientOptions>) -> Result<Self> {
+        let config = match options {
+            Some(options) => ClientConfig::try_from(options)?,
+            None => ClientConfig::default(),
+        };
+        Ok(Self {
+            client: Client::with_config(host, port as u16, config)?
+        })
+    }
+
+    #[napi]
+    pub fn connect(&mut self) -> Result<()> {
+        Ok(self.client.connect()?)
+    }
+
+    #[napi]
+    pub fn receive(&mut self) -> Result<Vec<u8>> {
+        Ok(self.client.receive()?)
+    }
+
+    #[napi]
+    pub fn frame_and_send(&mut self, data: Buffer, priority: Option<PacketPriority>) -> Result<()> {
+        let priority = priority.map(Priority::from).unwrap_or(Priority::Immediate);
+        self.client.send(data.to_vec(), Reliability::ReliableOrdered, 0, priority)?;
+        Ok(())
+    }
+
+    #[napi]
+    pub fn send(&mut self, data: Buffer, reliability: PacketReliability, channel: u32, priority: PacketPriority) -> Result<u32> {
+        let channel = u8::try_from(channel)
+            .map_err(|_| Error::new(Status::InvalidArg, format!("Invalid ordering channel: {}", channel)))?;
+        Ok(self.client.send(data.to_vec(), reliability.into(), channel, priority.into())?)
+    }
+
+    #[napi]
+    pub fn tick(&mut self) -> Result<()> {
+        Ok(self.client.tick()?)
+    }
+
+    #[napi]
+    pub fn ping(&mut self) -> Result<()> {
+        Ok(self.client.ping()?)
+    }
+    
+    #[napi]
+    pub fn disconnect(&mut self) -> Result<()> {
+        Ok(self.client.disconnect()?)
+    }
+
+    #[napi]
+    pub fn latency(&self) -> JsLatency {
+        let latency = self.client.latency();
+        JsLatency {
+            rtt: latency.rtt,
+            rtt_variance: latency.rtt_variance,
+            last_ping: latency.last_ping as f64,
+        }
+    }
+
+    #[napi]
+    pub fn is_connected(&self) -> bool {
+        self.client.is_connected()
+    }
+    
+    /// Returns the next event, a plain object discriminated by `type`.
+    // The union lives in events.d.ts, which the typegen leaves alone, so it can
+    // be imported by name as `ClientEvent`.
+    #[napi(js_name = "onEvent", ts_return_type = "import('./events').ClientEvent | null")]
+    pub fn on_event(&mut self, env: Env) -> Result<Option<Object>> {
+        match self.client.event_receiver.try_recv() {
+            Ok(event) => Ok(Some(event_to_js(&env, event)?)),
+            Err(_) => Ok(None)
+        }
+    }
+}
+
+/// Builds a plain object tagged by `type`, matching `ClientEvent` in events.d.ts.
+fn event_to_js(env: &Env, event: ClientEvent) -> Result<Object> {
+    let mut object = env.create_object()?;
+    object.set_named_property("type", env.create_string(event.name())?)?;
+    match event {
+        ClientEvent::Pong { server_guid, motd } => {
+            object.set_named_property("serverGuid", BigInt::from(server_guid))?;
+            object.set_named_property("motd", env.create_string(&motd)?)?;
+        }
+        ClientEvent::HandshakeProgress { state, mtu } => {
+            object.set_named_property("state", env.create_string(state.as_str())?)?;
+            object.set_named_property("mtu", env.create_uint32(mtu.into())?)?;
+        }
+        ClientEvent::ProtocolChanged { protocol } => {
+            object.set_named_property("protocol", env.create_uint32(protocol.into())?)?;
+        }
+        ClientEvent::Connected { mtu, server_guid } => {
+            object.set_named_property("mtu", env.create_uint32(mtu.into())?)?;
+            object.set_named_property("serverGuid", BigInt::from(server_guid))?;
+        }
+        ClientEvent::ConnectFailed { reason } => {
+            object.set_named_property("reason", env.create_string(reason.as_str())?)?;
+            match reason {
+                ConnectFailedReason::Timeout(stage) => {
+                    object.set_named_property("stage", env.create_string(stage.as_str())?)?;
+                }
+                ConnectFailedReason::IncompatibleProtocol { server_protocol } => {
+                    object.set_named_property("serverProtocol", env.create_uint32(server_protocol.into())?)?;
+                }
+                _ => {}
+            }
+        }
+        ClientEvent::Disconnected { reason } => {
+            object.set_named_property("reason", env.create_string(reason.as_str())?)?;
+        }
+        ClientEvent::Message { channel, reliability, payload } => {
+            object.set_named_property("channel", env.create_uint32(channel.into())?)?;
+            object.set_named_property("reliability", env.create_uint32(reliability as u32)?)?;
+            object.set_named_property("payload", env.create_buffer_with_data(payload)?.into_raw())?;
+        }
+        ClientEvent::LatencyUpdated { rtt } => {
+            object.set_named_property("rtt", env.create_double(rtt)?)?;
+        }
+        ClientEvent::ReceiptAcked { receipt } | ClientEvent::ReceiptLost { receipt } => {
+            object.set_named_property("receipt", env.create_uint32(receipt)?)?;
+        }
+        ClientEvent::SplitDropped { split_id, reason } => {
+            object.set_named_property("splitId", env.create_uint32(split_id.into())?)?;
+            object.set_named_property("reason", env.create_string(reason.as_str())?)?;
+        }
+        ClientEvent::UnknownPacket { id, payload } => {
+            object.set_named_property("id", env.create_uint32(id.into())?)?;
+            object.set_named_property("payload", env.create_buffer_with_data(payload)?.into_raw())?;
+        }
+    }
+    Ok(object)
+}
@@ -1,4 +1,8 @@
-use crate::{BinaryStream, Endianness};
+use crate::{BinaryError, BinaryStream, Endianness, FieldContext};
+
+/// The most sequence numbers an ACK or NACK may expand to. Ranges come from the
+/// peer, so without a cap one datagram could ask us to allocate gigabytes.
+pub const MAX_ACK_SEQUENCES: usize = 65536;
 
 #[derive(Debug)]
 pub struct Ack {
@@ -69,24 +73,26 @@ impl Ack {
         stream.binary
     }
 
-    pub fn deserialize(buffer: &[u8]) -> Self {
+    pub fn deserialize(buffer: &[u8]) -> Result<Self, BinaryError> {
         let mut stream = BinaryStream::new(Some(buffer.to_vec()), None);
-        let _id = stream.read_u8();
+        let _id = stream.read_u8().field("id")?;
         let mut sequences = Vec::new();
-        let records = stream.read_u16(None);
+        let records = stream.read_u16(None).field("records")?;
         for _ in 0..records {
-            let range = stream.read_bool();
+            let range = stream.read_bool().field("single")?;
             if range {
-                let r = stream.read_u24(Some(Endianness::Little));
+                let r = stream.read_u24(Some(Endianness::Little)).field("sequence")?;
                 sequences.push(r);
             } else {
-                let r = stream.read_u24(Some(Endianness::Little));
-                let l = stream.read_u24(Some(Endianness::Little));
-                for i in r..=l {
-                    sequences.push(i);
+                let r = stream.read_u24(Some(Endianness::Little)).field("start")?;
+                let l = stream.read_u24(Some(Endianness::Little)).field("end")?;
+                let count = sequences.len() + if l >= r { (l - r) as usize + 1 } else { 0 };
+                if count > MAX_ACK_SEQUENCES {
+                    return Err(BinaryError::TooLarge { field: "sequences", count, limit: MAX_ACK_SEQUENCES });
                 }
+                sequences.extend(r..=l);
             }
         }
-        Self { sequences }
+        Ok(Self { sequences })
     }
 }
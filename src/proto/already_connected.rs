@@ -0,0 +1,34 @@
+use crate::{BinaryError, BinaryStream, FieldContext};
+
+use super::MAGIC;
+
+/// The server still holds a session for this address or GUID.
+#[derive(Debug, Clone)]
+pub struct AlreadyConnected {
+    pub magic: [u8; 16],
+    pub guid: i64
+}
+
+impl AlreadyConnected {
+    pub const ID: u8 = 0x12;
+
+    pub fn new(guid: i64) -> Self {
+        Self { magic: MAGIC, guid }
+    }
+
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut stream = BinaryStream::new(None, None);
+        stream.write_u8(Self::ID);
+        stream.write(self.magic.to_vec());
+        stream.write_long(self.guid, None);
+        stream.binary
+    }
+
+    pub fn deserialize(data: &[u8]) -> Result<Self, BinaryError> {
+        let mut stream = BinaryStream::new(Some(data.to_vec()), None);
+        let _id = stream.read_u8().field("id")?;
+        let magic = stream.read(16).field("magic")?.try_into().unwrap();
+        let guid = stream.read_long(None).field("guid")?;
+        Ok(Self { magic, guid })
+    }
+}
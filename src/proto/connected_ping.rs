@@ -1,4 +1,4 @@
-use crate::BinaryStream;
+use crate::{BinaryError, BinaryStream, FieldContext};
 
 #[derive(Debug, Clone)]
 pub struct ConnectedPing {
@@ -8,6 +8,10 @@ pub struct ConnectedPing {
 impl ConnectedPing {
     pub const ID: u8 = 0x00;
 
+    pub fn new(timestamp: i64) -> Self {
+        Self { timestamp }
+    }
+
     pub fn serialize(&self) -> Vec<u8> {
         let mut stream = BinaryStream::new(None, None);
         stream.write_u8(Self::ID);
@@ -15,11 +19,11 @@ impl ConnectedPing {
         stream.binary
     }
 
-    pub fn deserialize(binary: &[u8]) -> Self {
+    pub fn deserialize(binary: &[u8]) -> Result<Self, BinaryError> {
         let mut stream = BinaryStream::new(Some(binary.to_vec()), None);
         stream.skip(1);
-        let timestamp = stream.read_long(None);
-        Self { timestamp }
+        let timestamp = stream.read_long(None).field("timestamp")?;
+        Ok(Self { timestamp })
     }
 }
 
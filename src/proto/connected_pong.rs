@@ -1,4 +1,4 @@
-use crate::BinaryStream;
+use crate::{BinaryError, BinaryStream, FieldContext};
 
 #[derive(Debug, Clone)]
 pub struct ConnectedPong {
@@ -21,12 +21,12 @@ impl ConnectedPong {
         stream.binary
     }
 
-    pub fn deserialize(binary: &[u8]) -> Self {
+    pub fn deserialize(binary: &[u8]) -> Result<Self, BinaryError> {
         let mut stream = BinaryStream::new(Some(binary.to_vec()), None);
         stream.skip(1);
-        let ping_timestamp = stream.read_long(None);
-        let pong_timestamp = stream.read_long(None);
-        Self { ping_timestamp, pong_timestamp }
+        let ping_timestamp = stream.read_long(None).field("ping_timestamp")?;
+        let pong_timestamp = stream.read_long(None).field("pong_timestamp")?;
+        Ok(Self { ping_timestamp, pong_timestamp })
     }
 }
 
@@ -1,4 +1,6 @@
-use crate::BinaryStream;
+use crate::{BinaryError, BinaryStream, FieldContext};
+
+use super::MAGIC;
 
 #[derive(Debug, Clone)]
 pub struct ConnectionReplyOne {
@@ -12,18 +14,31 @@ pub struct ConnectionReplyOne {
 impl ConnectionReplyOne {
     pub const ID: u8 = 0x06;
 
-    pub fn serialize() { 
+    pub fn new(guid: i64, security: bool, cookie: Option<u32>, mtu_size: u16) -> Self {
+        Self { magic: MAGIC, guid, security, cookie, mtu_size }
+    }
 
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut stream = BinaryStream::new(None, None);
+        stream.write_u8(Self::ID);
+        stream.write(self.magic.to_vec());
+        stream.write_long(self.guid, None);
+        stream.write_bool(self.security);
+        if let Some(cookie) = self.cookie {
+            stream.write_u32(cookie, None);
+        }
+        stream.write_u16(self.mtu_size, None);
+        stream.binary
     }
 
-    pub fn deserialize(data: Vec<u8>) -> Result<Self, String> {
+    pub fn deserialize(data: Vec<u8>) -> Result<Self, BinaryError> {
         let mut stream = BinaryStream::new(Some(data), None);
         stream.skip(1);
-        let magic = stream.read(16).try_into().unwrap();
-        let guid = stream.read_long(None);
-        let security = stream.read_bool();
-        let cookie = if security { Some(stream.read_u32(None)) } else { None };
-        let mtu_size = stream.read_u16(None);
+        let magic = stream.read(16).field("magic")?.try_into().unwrap();
+        let guid = stream.read_long(None).field("guid")?;
+        let security = stream.read_bool().field("security")?;
+        let cookie = if security { Some(stream.read_u32(None).field("cookie")?) } else { None };
+        let mtu_size = stream.read_u16(None).field("mtu_size")?;
         Ok(Self { magic, guid, security, cookie, mtu_size })
     }
 }
\ No newline at end of file
@@ -1,4 +1,4 @@
-use crate::BinaryStream;
+use crate::{BinaryError, BinaryStream, FieldContext};
 
 pub struct ConnectionRequest { 
     pub guid: i64,
@@ -22,12 +22,12 @@ impl ConnectionRequest {
         stream.binary
     }
 
-    pub fn deserialize(binary: &[u8]) -> Self {
+    pub fn deserialize(binary: &[u8]) -> Result<Self, BinaryError> {
         let mut stream = BinaryStream::new(Some(binary.to_vec()), None);
         stream.skip(1);
-        let guid = stream.read_long(None);
-        let timestamp = stream.read_long(None);
-        let security = stream.read_bool();
-        Self::new(guid, timestamp, security)
+        let guid = stream.read_long(None).field("guid")?;
+        let timestamp = stream.read_long(None).field("timestamp")?;
+        let security = stream.read_bool().field("security")?;
+        Ok(Self::new(guid, timestamp, security))
     }
 }
@@ -1,4 +1,4 @@
-use crate::BinaryStream;
+use crate::{BinaryError, BinaryStream, FieldContext};
 
 use super::Address;
 
@@ -21,21 +21,27 @@ impl ConnectionRequestAccepted {
     pub fn serialize(&self) -> Vec<u8> {
         let mut stream = BinaryStream::new(None, None);
         stream.write_u8(Self::ID);
-        // TODO: Implement
+        self.client_address.write(&mut stream);
+        stream.write_u16(self.client_id, None);
+        for address in &self.server_addresses {
+            address.write(&mut stream);
+        }
+        stream.write_long(self.client_send_time, None);
+        stream.write_long(self.server_send_time, None);
         stream.binary
     }
 
-    pub fn deserialize(buffer: &[u8]) -> Result<Self, String> {
+    pub fn deserialize(buffer: &[u8]) -> Result<Self, BinaryError> {
         let mut stream = BinaryStream::new(Some(buffer.to_vec()), Some(0));
         stream.skip(1);
-        let client_address = Address::read(&mut stream);
-        let client_id = stream.read_u16(None);
+        let client_address = Address::read(&mut stream)?;
+        let client_id = stream.read_u16(None).field("client_id")?;
         let mut server_addresses = Vec::new();
         for _ in 0..20 {
-            server_addresses.push(Address::read(&mut stream));
+            server_addresses.push(Address::read(&mut stream)?);
         }        
-        let client_send_time = stream.read_long(None);
-        let server_send_time = stream.read_long(None);
+        let client_send_time = stream.read_long(None).field("client_send_time")?;
+        let server_send_time = stream.read_long(None).field("server_send_time")?;
         Ok(Self::new(client_address, client_id, server_addresses, client_send_time, server_send_time))
     }
 }
\ No newline at end of file
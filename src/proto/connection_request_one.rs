@@ -1,4 +1,4 @@
-use crate::BinaryStream;
+use crate::{BinaryError, BinaryStream, FieldContext};
 
 use super::MAGIC;
 
@@ -18,12 +18,22 @@ impl ConnectionRequestOne {
     pub fn serialize(&self) -> Vec<u8> {
         let mut stream = BinaryStream::new(None, None);
         stream.write_u8(Self::ID);
-        stream.write((&self.magic).to_vec());
+        stream.write(self.magic.to_vec());
         stream.write_u8 (self.protocol);
-        let udp_overhead = 28 as u16;
+        let udp_overhead = 28_u16;
         let current_size = stream.binary.len() as u16;
-        let padding_size = self.mtu_size - udp_overhead - current_size;
+        let padding_size = self.mtu_size.saturating_sub(udp_overhead + current_size);
         stream.write(vec![0; padding_size as usize]);
         stream.binary
     }
+
+    pub fn deserialize(data: Vec<u8>) -> Result<Self, BinaryError> {
+        let udp_overhead = 28_u16;
+        let mtu_size = data.len() as u16 + udp_overhead;
+        let mut stream = BinaryStream::new(Some(data), None);
+        stream.skip(1);
+        let magic = stream.read(16).field("magic")?.try_into().unwrap();
+        let protocol = stream.read_u8().field("protocol")?;
+        Ok(Self { magic, protocol, mtu_size })
+    }
 }
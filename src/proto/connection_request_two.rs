@@ -1,4 +1,4 @@
-use crate::BinaryStream;
+use crate::{BinaryError, BinaryStream, FieldContext};
 
 use super::{Address, MAGIC};
 
@@ -19,20 +19,20 @@ impl ConnectionRequestTwo {
     pub fn serialize(&self) -> Vec<u8> {
         let mut stream = BinaryStream::new(None, None);
         stream.write_u8(Self::ID);
-        stream.write((&self.magic).to_vec());
+        stream.write(self.magic.to_vec());
         self.address.write(&mut stream);
         stream.write_u16(self.mtu_size, None);
         stream.write_long(self.guid, None);
         stream.binary
     }
 
-    pub fn deserialize(data: Vec<u8>) -> Result<Self, String> {
+    pub fn deserialize(data: Vec<u8>) -> Result<Self, BinaryError> {
         let mut stream = BinaryStream::new(Some(data), None);
         stream.skip(1);
-        let magic = stream.read(16).try_into().unwrap();
-        let address = Address::read(&mut stream);
-        let mtu_size = stream.read_u16(None);
-        let guid = stream.read_long(None);
+        let magic = stream.read(16).field("magic")?.try_into().unwrap();
+        let address = Address::read(&mut stream)?;
+        let mtu_size = stream.read_u16(None).field("mtu_size")?;
+        let guid = stream.read_long(None).field("guid")?;
         Ok(Self { magic, address, mtu_size, guid })
     }
 }
@@ -0,0 +1,24 @@
+use crate::{BinaryError, BinaryStream, FieldContext};
+
+#[derive(Debug, Clone, Default)]
+pub struct DisconnectionNotification {}
+
+impl DisconnectionNotification {
+    pub const ID: u8 = 0x15;
+
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut stream = BinaryStream::new(None, None);
+        stream.write_u8(Self::ID);
+        stream.binary
+    }
+
+    pub fn deserialize(binary: &[u8]) -> Result<Self, BinaryError> {
+        let mut stream = BinaryStream::new(Some(binary.to_vec()), None);
+        let _id = stream.read_u8().field("id")?;
+        Ok(Self::new())
+    }
+}
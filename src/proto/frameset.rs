@@ -1,4 +1,4 @@
-use crate::{BinaryStream, Endianness};
+use crate::{BinaryError, BinaryStream, Endianness, FieldContext};
 
 use super::Frame;
 
@@ -24,14 +24,14 @@ impl FrameSet {
         stream.binary
     }
 
-    pub fn deserialize(buffer: &[u8]) -> Result<Self, String> {
+    pub fn deserialize(buffer: &[u8]) -> Result<Self, BinaryError> {
         let mut stream: BinaryStream = BinaryStream::new(Some(buffer.to_vec()), Some(0));
         stream.skip(1);
-        let sequence = stream.read_u24(Some(Endianness::Little));
+        let sequence = stream.read_u24(Some(Endianness::Little)).field("sequence")?;
         let mut frames = Vec::new();
         while !stream.cursor_at_end() {
-            let frame = Frame::read(&mut stream);
-            if frame.payload.len() == 0 {
+            let frame = Frame::read(&mut stream)?;
+            if frame.payload.is_empty() {
                 break;
             }
             frames.push(frame);
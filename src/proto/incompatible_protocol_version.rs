@@ -0,0 +1,37 @@
+use crate::{BinaryError, BinaryStream, FieldContext};
+
+use super::MAGIC;
+
+/// The server speaks a different RakNet protocol version; `protocol` is its own.
+#[derive(Debug, Clone)]
+pub struct IncompatibleProtocolVersion {
+    pub protocol: u8,
+    pub magic: [u8; 16],
+    pub guid: i64
+}
+
+impl IncompatibleProtocolVersion {
+    pub const ID: u8 = 0x19;
+
+    pub fn new(protocol: u8, guid: i64) -> Self {
+        Self { protocol, magic: MAGIC, guid }
+    }
+
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut stream = BinaryStream::new(None, None);
+        stream.write_u8(Self::ID);
+        stream.write_u8(self.protocol);
+        stream.write(self.magic.to_vec());
+        stream.write_long(self.guid, None);
+        stream.binary
+    }
+
+    pub fn deserialize(data: &[u8]) -> Result<Self, BinaryError> {
+        let mut stream = BinaryStream::new(Some(data.to_vec()), None);
+        let _id = stream.read_u8().field("id")?;
+        let protocol = stream.read_u8().field("protocol")?;
+        let magic = stream.read(16).field("magic")?.try_into().unwrap();
+        let guid = stream.read_long(None).field("guid")?;
+        Ok(Self { protocol, magic, guid })
+    }
+}
@@ -13,8 +13,15 @@ mod connection_request_accepted;
 mod connected_ping;
 mod connected_pong;
 mod new_incomming_connection;
+mod disconnection_notification;
 mod ack;
 mod nack;
+mod incompatible_protocol_version;
+mod connection_banned;
+mod no_free_incoming_connections;
+mod already_connected;
+mod connection_attempt_failed;
+mod ip_recently_connected;
 
 pub use packet::*;
 pub use types::*;
@@ -30,5 +37,12 @@ pub use connection_request_accepted::*;
 pub use connected_ping::*;
 pub use connected_pong::*;
 pub use new_incomming_connection::*;
+pub use disconnection_notification::*;
 pub use ack::*;
 pub use nack::*;
+pub use incompatible_protocol_version::*;
+pub use connection_banned::*;
+pub use no_free_incoming_connections::*;
+pub use already_connected::*;
+pub use connection_attempt_failed::*;
+pub use ip_recently_connected::*;
@@ -1,4 +1,4 @@
-use crate::BinaryStream;
+use crate::{BinaryError, BinaryStream, FieldContext};
 
 use super::Address;
 
@@ -29,11 +29,14 @@ impl NewIncommingConnection {
         stream.binary
     }
 
-    pub fn deserialize(stream: &mut BinaryStream) -> Self {
-        let server_address = Address::read(stream);
-        let internal_addresses = core::array::from_fn(|_| Address::read(stream));
-        let incoming_timestamp = stream.read_long(None);
-        let server_timestamp = stream.read_long(None);
-        Self::new(server_address, internal_addresses, incoming_timestamp, server_timestamp)
+    pub fn deserialize(stream: &mut BinaryStream) -> Result<Self, BinaryError> {
+        let server_address = Address::read(stream)?;
+        let mut internal_addresses: [Address; 20] = Default::default();
+        for address in internal_addresses.iter_mut() {
+            *address = Address::read(stream)?;
+        }
+        let incoming_timestamp = stream.read_long(None).field("incoming_timestamp")?;
+        let server_timestamp = stream.read_long(None).field("server_timestamp")?;
+        Ok(Self::new(server_address, internal_addresses, incoming_timestamp, server_timestamp))
     }
 }
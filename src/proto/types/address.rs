@@ -1,48 +1,58 @@
-use std::net::{IpAddr, Ipv6Addr};
+use std::net::{IpAddr, Ipv6Addr, SocketAddr};
 
-use crate::BinaryStream;
+use crate::{BinaryError, BinaryStream, FieldContext};
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone)]
 pub struct Address {
     pub version: u8,
     pub address: String,
     pub port: u16
 }
 
+impl Default for Address {
+    fn default() -> Self {
+        Self { version: 4, address: "0.0.0.0".to_string(), port: 0 }
+    }
+}
+
 impl Address {
     pub fn new(version: u8, address: String, port: u16) -> Self {
         Self { version, address, port }
     }
 
-    pub fn default() -> Self {
-        Self { version: 4, address: "0.0.0.0".to_string(), port: 0 }
+    pub fn from_socket_address(address: &SocketAddr) -> Self {
+        match address {
+            SocketAddr::V4(v4) => Self::new(4, v4.ip().to_string(), v4.port()),
+            SocketAddr::V6(v6) => {
+                let segments: Vec<String> = v6.ip().segments()
+                    .iter()
+                    .map(|segment| format!("{:x}", segment))
+                    .collect();
+                Self::new(6, segments.join(":"), v6.port())
+            }
+        }
     }
 
-    pub fn read(stream: &mut BinaryStream) -> Self {
-        let version = stream.read_u8();
+    pub fn read(stream: &mut BinaryStream) -> Result<Self, BinaryError> {
+        let version = stream.read_u8().field("address.version")?;
         if version == 4 {
-            let address = format!(
-                "{}.{}.{}.{}",
-                !stream.read_byte() & 0xff,
-                !stream.read_byte() & 0xff,
-                !stream.read_byte() & 0xff,
-                !stream.read_byte() & 0xff
-            );
-            let port = stream.read_short(None);
-            Self { version, address, port }
+            let bytes = stream.read(4).field("address.ip")?;
+            let address = format!("{}.{}.{}.{}", !bytes[0], !bytes[1], !bytes[2], !bytes[3]);
+            let port = stream.read_short(None).field("address.port")?;
+            Ok(Self { version, address, port })
         } else if version == 6 {
-            let port = stream.read_u16(None);
-            stream.read_u32(None); 
-            let bytes = stream.read(16);
-            stream.read_u32(None);
+            let port = stream.read_u16(None).field("address.port")?;
+            stream.read_u32(None).field("address.flow_info")?; 
+            let bytes = stream.read(16).field("address.ip")?;
+            stream.read_u32(None).field("address.scope_id")?;
             
-            let byte_array: [u8; 16] = bytes.try_into().expect("Incorrect length for IPv6 address");
+            let byte_array: [u8; 16] = bytes.try_into().unwrap();
             let ipv6 = Ipv6Addr::from(byte_array);
             let address = IpAddr::V6(ipv6).to_string();
         
-            Self { address, port, version }
+            Ok(Self { address, port, version })
         } else {
-            Self { address: "".to_string(), port: 0, version }
+            Ok(Self { address: "".to_string(), port: 0, version })
         }
     }
 
@@ -54,7 +64,7 @@ impl Address {
             assert_eq!(parts.len(), 4, "Wrong number of parts in IPv4 IP, expected 4, got {}", parts.len());
             for part in parts {
                 let b: u8 = part.parse().unwrap();
-                stream.write_u8((!b) & 0xff);
+                stream.write_u8(!b);
             }
             stream.write_u16(self.port, None);
             
@@ -1,4 +1,4 @@
-use crate::{BinaryStream, Endianness};
+use crate::{BinaryError, BinaryStream, Endianness, FieldContext};
 
 use super::{Flags, Reliability};
 
@@ -13,6 +13,14 @@ pub struct Frame {
     pub split_frame_index: Option<u32>,
     pub split_id: Option<u16>,
     pub split_size: Option<u32>,
+    /// Local receipt number for the ack-receipt reliabilities; never written to the wire.
+    pub receipt: Option<u32>,
+}
+
+impl Default for Frame {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Frame {
@@ -29,7 +37,7 @@ impl Frame {
         if self.reliability.is_sequenced() {
             size += 3; // sequence_frame_index
         }
-        if self.reliability.is_ordered() {
+        if self.reliability.is_sequenced() || self.reliability.is_ordered() {
             size += 4; // ordered_frame_index + order_channel
         }
         if self.is_split() {
@@ -38,35 +46,46 @@ impl Frame {
         size
     }
 
-    pub fn read(stream: &mut BinaryStream) -> Self {
-        let flags = stream.read_u8();
+    pub fn read(stream: &mut BinaryStream) -> Result<Self, BinaryError> {
+        let flags = stream.read_u8().field("frame.flags")?;
         let reliability = Reliability::from_u8((flags & 0xe0) >> 5);
         let split = (flags & Flags::Split as u8) != 0;
-        let bits = stream.read_u16(None);
+        let bits = stream.read_u16(None).field("frame.length")?;
         let length = ((bits as f32) / 8.0).ceil() as usize;
         let reliable_frame_index = if reliability.is_reliable() { 
-            Some(stream.read_u24(Some(Endianness::Little))) 
+            Some(stream.read_u24(Some(Endianness::Little)).field("frame.reliable_frame_index")?) 
         } else { 
             None 
         };
-        let sequence_frame_index = if reliability.is_sequenced() { Some(stream.read_u24(Some(Endianness::Little))) } else { None };
-        let (ordered_frame_index, order_channel) = if reliability.is_ordered() {
-            (Some(stream.read_u24(Some(Endianness::Little))), Some(stream.read_u8()))
+        let sequence_frame_index = if reliability.is_sequenced() {
+            Some(stream.read_u24(Some(Endianness::Little)).field("frame.sequence_frame_index")?)
+        } else { None };
+        let (ordered_frame_index, order_channel) = if reliability.is_sequenced() || reliability.is_ordered() {
+            (
+                Some(stream.read_u24(Some(Endianness::Little)).field("frame.ordered_frame_index")?),
+                Some(stream.read_u8().field("frame.order_channel")?)
+            )
         } else { (None, None) };
         let (split_size, split_id, split_frame_index) = if split {
-            (Some(stream.read_u32(None)), Some(stream.read_u16(None)), Some(stream.read_u32(None)))
+            (
+                Some(stream.read_u32(None).field("frame.split_size")?),
+                Some(stream.read_u16(None).field("frame.split_id")?),
+                Some(stream.read_u32(None).field("frame.split_frame_index")?)
+            )
         } else { (None, None, None) };
-        let payload = stream.read(length as usize);
-        Self {
+        let payload = stream.read(length).field("frame.payload")?;
+        Ok(Self {
             reliable_frame_index, sequence_frame_index, ordered_frame_index,
             order_channel, reliability, payload,
             split_frame_index, split_id, split_size,
-        }
+            receipt: None,
+        })
     }
 
 
     pub fn write(&self, stream: &mut BinaryStream) {
-        let flags = (self.reliability.clone() as u8) << 5 |
+        // Peers only ever see the plain reliability; receipts are tracked locally.
+        let flags = (self.reliability.without_ack_receipt() as u8) << 5 |
             if self.is_split() { Flags::Split as u8 } else { 0 };
         stream.write_u8(flags);
         stream.write_u16((self.payload.len() as u16) << 3, None);
@@ -76,7 +95,7 @@ impl Frame {
         if self.reliability.is_sequenced() {
             stream.write_u24(self.sequence_frame_index.unwrap(), Some(Endianness::Little));
         }
-        if self.reliability.is_ordered() {
+        if self.reliability.is_sequenced() || self.reliability.is_ordered() {
             stream.write_u24(self.ordered_frame_index.unwrap(), Some(Endianness::Little));
             stream.write_u8(self.order_channel.unwrap());
         }
@@ -100,6 +119,7 @@ impl Frame {
             split_frame_index: None, 
             split_id: None, 
             split_size: None,
+            receipt: None,
         }
     }
 }
@@ -3,9 +3,11 @@ mod frame;
 mod reliability;
 mod flags;
 mod priority;
+mod serial;
 
 pub use address::*;
 pub use frame::*;
 pub use reliability::*;
 pub use flags::*;
-pub use priority::*;
\ No newline at end of file
+pub use priority::*;
+pub use serial::*;
\ No newline at end of file
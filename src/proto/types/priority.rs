@@ -1,5 +1,34 @@
-#[derive(PartialEq, Clone)]
+#[derive(Debug, Clone, Copy, Hash, Eq, PartialEq, Default)]
 pub enum Priority {
-    Normal,
-    Immediate
-}
\ No newline at end of file
+    Immediate = 0,
+    High = 1,
+    #[default]
+    Medium = 2,
+    Low = 3
+}
+
+impl Priority {
+    pub const COUNT: usize = 4;
+
+    pub fn from_u8(value: u8) -> Self {
+        match value {
+            0 => Self::Immediate,
+            1 => Self::High,
+            2 => Self::Medium,
+            _ => Self::Low
+        }
+    }
+
+    /// How far a level's weight advances per frame sent, as in RakNet's
+    /// ReliabilityLayer: lower levels advance faster, so they get a smaller
+    /// share of each datagram without ever being shut out.
+    pub fn weight_step(&self) -> u64 {
+        let level = *self as u64;
+        (1 << level) * (level + 1) + level
+    }
+
+    pub fn initial_weight(&self) -> u64 {
+        let level = *self as u64;
+        (1 << level) * level + level
+    }
+}
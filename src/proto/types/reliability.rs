@@ -35,6 +35,23 @@ impl Reliability {
         )
     }
 
+    pub fn is_ack_receipt(&self) -> bool {
+        matches!(self,
+            Self::UnreliableWithAckReceipt |
+            Self::ReliableWithAckReceipt |
+            Self::ReliableOrderedWithAckReceipt
+        )
+    }
+
+    pub fn without_ack_receipt(&self) -> Self {
+        match self {
+            Self::UnreliableWithAckReceipt => Self::Unreliable,
+            Self::ReliableWithAckReceipt => Self::Reliable,
+            Self::ReliableOrderedWithAckReceipt => Self::ReliableOrdered,
+            reliability => *reliability,
+        }
+    }
+
     pub fn is_sequenced(&self) -> bool {
         matches!(self, Self::ReliableSequenced | Self::UnreliableSequenced)
     }
@@ -0,0 +1,29 @@
+//! Serial-number arithmetic (RFC 1982) for the 24-bit counters RakNet puts on
+//! the wire: datagram sequence, reliable, order and sequence indices.
+
+pub const U24_MASK: u32 = 0x00ff_ffff;
+const U24_HALF: u32 = 0x0080_0000;
+
+pub fn serial_add(value: u32, amount: u32) -> u32 {
+    value.wrapping_add(amount) & U24_MASK
+}
+
+pub fn serial_next(value: u32) -> u32 {
+    serial_add(value, 1)
+}
+
+/// How many steps forward it takes to get from `from` to `to`.
+pub fn serial_distance(from: u32, to: u32) -> u32 {
+    to.wrapping_sub(from) & U24_MASK
+}
+
+/// Whether `a` comes before `b`, treating anything less than half the space
+/// ahead as "after".
+pub fn serial_lt(a: u32, b: u32) -> bool {
+    let distance = serial_distance(a, b);
+    distance != 0 && distance < U24_HALF
+}
+
+pub fn serial_gt(a: u32, b: u32) -> bool {
+    serial_lt(b, a)
+}
@@ -1,4 +1,4 @@
-use crate::BinaryStream;
+use crate::{BinaryError, BinaryStream, FieldContext};
 
 use super::MAGIC;
 
@@ -18,19 +18,19 @@ impl UnconnectedPing {
     pub fn serialize(&self) -> Vec<u8> {
         let mut stream = BinaryStream::new(None, None);
         stream.write_u8(Self::ID);
-        stream.write_long(self.timestamp.try_into().unwrap(), None);
-        stream.write((&self.magic).to_vec());
+        stream.write_long(self.timestamp, None);
+        stream.write(self.magic.to_vec());
         stream.write_long(self.guid, None);
         stream.binary
     }
 
-    pub fn deserialize(data: &[u8]) -> Self {
+    pub fn deserialize(data: &[u8]) -> Result<Self, BinaryError> {
         let mut stream = BinaryStream::new(Some(data.to_vec()), None);
-        let _id = stream.read_u8();
-        let timestamp = stream.read_long(None).try_into().unwrap();
-        let magic = stream.read(16);
-        let guid = stream.read_long(None).try_into().unwrap();
+        let _id = stream.read_u8().field("id")?;
+        let timestamp = stream.read_long(None).field("timestamp")?;
+        let magic = stream.read(16).field("magic")?;
+        let guid = stream.read_long(None).field("guid")?;
 
-        Self { timestamp, magic: magic.try_into().unwrap(), guid }
+        Ok(Self { timestamp, magic: magic.try_into().unwrap(), guid })
     }
 }
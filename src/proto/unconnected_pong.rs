@@ -1,4 +1,4 @@
-use crate::BinaryStream;
+use crate::{BinaryError, BinaryStream, FieldContext};
 
 use super::MAGIC;
 
@@ -20,22 +20,22 @@ impl UnconnectedPong {
     pub fn serialize(&self) -> Vec<u8> {
         let mut stream = BinaryStream::new(None, None);
         stream.write_u8(Self::ID);
-        stream.write_long(self.timestamp.try_into().unwrap(), None);
-        stream.write((&self.magic).to_vec());
-        stream.write_long(self.guid.try_into().unwrap(), None);
+        stream.write_long(self.timestamp as i64, None);
+        stream.write_long(self.guid as i64, None);
+        stream.write(self.magic.to_vec());
         stream.write_string16(&self.message, None);
         stream.binary
     }
 
 
-    pub fn deserialize(data: Vec<u8>) -> Result<Self, String> {
+    pub fn deserialize(data: Vec<u8>) -> Result<Self, BinaryError> {
         let mut stream = BinaryStream::new(Some(data), None);
         stream.skip(1);
         Ok(Self { 
-            timestamp: stream.read_long(None).try_into().unwrap(), 
-            guid: stream.read_long(None).try_into().unwrap(), 
-            magic: stream.read(16).try_into().unwrap(), 
-            message: stream.read_string16(None)? 
+            timestamp: stream.read_long(None).field("timestamp")? as u64, 
+            guid: stream.read_long(None).field("guid")? as u64, 
+            magic: stream.read(16).field("magic")?.try_into().unwrap(), 
+            message: stream.read_string16(None).field("message")? 
         })
     }
 }
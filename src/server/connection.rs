@@ -0,0 +1,104 @@
+use std::net::SocketAddr;
+use std::sync::mpsc::Sender;
+
+
+use crate::client::DisconnectReason;
+use crate::error::RakError;
+use crate::framer::Framer;
+use crate::proto::{
+    Address, ConnectedPing, ConnectedPong, ConnectionRequest, ConnectionRequestAccepted, DisconnectionNotification, Frame, NewIncommingConnection, Priority
+};
+
+use super::ServerEvent;
+
+pub struct Connection {
+    pub address: SocketAddr,
+    pub framer: Framer,
+    pub event_sender: Sender<ServerEvent>,
+    pub connected: bool,
+    pub disconnected: bool,
+    pub last_received_at: u64,
+}
+
+impl Connection {
+    pub fn new(address: SocketAddr, mtu_size: u16, event_sender: Sender<ServerEvent>, now: u64) -> Self {
+        Self {
+            address,
+            framer: Framer::new(mtu_size),
+            event_sender,
+            connected: false,
+            disconnected: false,
+            last_received_at: now,
+        }
+    }
+
+    pub fn frame_and_send(&mut self, data: Vec<u8>) -> Result<(), RakError> {
+        let mut frame = self.framer.pls_frame(data);
+        self.framer.send_frame(&mut frame, Some(Priority::Immediate))?;
+        Ok(())
+    }
+
+    pub fn emit_event(&self, event: ServerEvent) {
+        self.event_sender.send(event).unwrap();
+    }
+
+    pub fn receive(&mut self, datagram: &[u8], now: u64) -> Result<(), RakError> {
+        self.last_received_at = now;
+        self.framer.receive(datagram, now)?;
+        while let Some(frame) = self.framer.poll_message() {
+            self.handle_message(&frame)?;
+        }
+        self.emit_split_drops();
+        Ok(())
+    }
+
+    pub fn emit_split_drops(&mut self) {
+        while let Some(drop) = self.framer.poll_split_drop() {
+            self.emit_event(ServerEvent::SplitDropped { address: self.address, split_id: drop.split_id, reason: drop.reason });
+        }
+    }
+
+    pub fn handle_message(&mut self, frame: &Frame) -> Result<(), RakError> {
+        if frame.payload.is_empty() {
+            return Ok(());
+        }
+        let packet_id = frame.payload[0];
+
+        match packet_id {
+            ConnectedPing::ID => {
+                let packet = ConnectedPing::deserialize(&frame.payload)?;
+                let pong = ConnectedPong::new(packet.timestamp, self.framer.current_time as i64);
+                self.frame_and_send(pong.serialize())?;
+            }
+            ConnectionRequest::ID => {
+                let packet = ConnectionRequest::deserialize(&frame.payload)?;
+                let server_addresses = (0..20).map(|_| Address::default()).collect();
+                let response = ConnectionRequestAccepted::new(
+                    Address::from_socket_address(&self.address),
+                    0,
+                    server_addresses,
+                    packet.timestamp,
+                    self.framer.current_time as i64
+                );
+                self.frame_and_send(response.serialize())?;
+            }
+            NewIncommingConnection::ID => {
+                self.connected = true;
+                self.emit_event(ServerEvent::Connected { address: self.address });
+            }
+            DisconnectionNotification::ID => {
+                self.disconnected = true;
+                self.emit_event(ServerEvent::Disconnected { address: self.address, reason: DisconnectReason::ClientRequested });
+            }
+            _ => {
+                self.emit_event(ServerEvent::Message {
+                    address: self.address,
+                    channel: frame.order_channel.unwrap_or(0),
+                    reliability: frame.reliability,
+                    payload: frame.payload.to_vec(),
+                });
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,46 @@
+use std::net::SocketAddr;
+
+use crate::client::DisconnectReason;
+use crate::framer::SplitDropReason;
+use crate::Reliability;
+
+/// Everything a `Server` reports through its event channel, each tagged with
+/// the address of the peer it concerns.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ServerEvent {
+    /// A peer finished the offline handshake and a session was opened for it.
+    Connect { address: SocketAddr, mtu: u16, client_guid: i64 },
+    /// The peer completed the online handshake.
+    Connected { address: SocketAddr },
+    /// An application packet delivered by the reliability layer.
+    Message { address: SocketAddr, channel: u8, reliability: Reliability, payload: Vec<u8> },
+    Disconnected { address: SocketAddr, reason: DisconnectReason },
+    SplitDropped { address: SocketAddr, split_id: u16, reason: SplitDropReason },
+    /// An offline packet the server does not handle.
+    UnknownPacket { address: SocketAddr, id: u8, payload: Vec<u8> },
+}
+
+impl ServerEvent {
+    pub fn address(&self) -> SocketAddr {
+        match self {
+            Self::Connect { address, .. }
+            | Self::Connected { address }
+            | Self::Message { address, .. }
+            | Self::Disconnected { address, .. }
+            | Self::SplitDropped { address, .. }
+            | Self::UnknownPacket { address, .. } => *address,
+        }
+    }
+
+    /// The snake_case tag of the variant, matching `ClientEvent::name`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Connect { .. } => "connect",
+            Self::Connected { .. } => "connected",
+            Self::Message { .. } => "message",
+            Self::Disconnected { .. } => "disconnected",
+            Self::SplitDropped { .. } => "split_dropped",
+            Self::UnknownPacket { .. } => "unknown_packet",
+        }
+    }
+}
@@ -0,0 +1,8 @@
+#[allow(clippy::module_inception)]
+mod server;
+mod connection;
+mod event;
+
+pub use server::*;
+pub use connection::*;
+pub use event::*;
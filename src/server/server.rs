@@ -0,0 +1,183 @@
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::Arc;
+
+use crate::proto::{
+    Ack, Address, AlreadyConnected, ConnectionReplyOne, ConnectionReplyTwo, ConnectionRequestOne, ConnectionRequestTwo, FrameSet,
+    Nack, NoFreeIncomingConnections, UnconnectedPing, UnconnectedPong
+};
+use crate::client::DisconnectReason;
+use crate::error::RakError;
+use crate::socket::Socket;
+use crate::clock::{Clock, SystemClock};
+use crate::framer::MIN_MTU_SIZE;
+
+use super::{Connection, ServerEvent};
+
+pub struct Server {
+    pub socket: Arc<Socket>,
+    pub guid: i64,
+    pub protocol: u8,
+    pub max_mtu_size: u16,
+    pub message: String,
+    pub connections: HashMap<SocketAddr, Connection>,
+    /// Further handshakes are turned away with `NoFreeIncomingConnections`.
+    pub max_connections: usize,
+    /// A session that has received nothing for this many milliseconds is dropped.
+    pub session_timeout: u64,
+    pub event_sender: Sender<ServerEvent>,
+    pub event_receiver: Receiver<ServerEvent>,
+    pub clock: Box<dyn Clock>,
+}
+
+impl Server {
+    pub fn new(address: String, port: u16) -> Result<Self, RakError> {
+        let socket = Arc::new(Socket::bind(address, port)?);
+        let (event_sender, event_receiver) = channel();
+
+        Ok(Self {
+            socket,
+            guid: rand::random::<i64>(),
+            protocol: 11,
+            max_mtu_size: 1492,
+            message: String::new(),
+            connections: HashMap::new(),
+            max_connections: 1024,
+            session_timeout: 10000,
+            event_sender,
+            event_receiver,
+            clock: Box::new(SystemClock::new()),
+        })
+    }
+
+    pub fn receive(&mut self) -> Result<Vec<u8>, RakError> {
+        let mut buffer = [0; 1500];
+        match self.socket.receive_from(&mut buffer)? {
+            None => Ok(vec![]),
+            Some((size, address)) => {
+                self.handle_packet(&buffer[..size], address)?;
+                Ok(buffer[..size].to_vec())
+            }
+        }
+    }
+
+    pub fn tick(&mut self) -> Result<(), RakError> {
+        let now = self.now();
+        for connection in self.connections.values_mut() {
+            if !connection.disconnected && now.saturating_sub(connection.last_received_at) >= self.session_timeout {
+                connection.disconnected = true;
+                connection.emit_event(ServerEvent::Disconnected { address: connection.address, reason: DisconnectReason::Timeout });
+                continue;
+            }
+            connection.framer.tick(now);
+            connection.emit_split_drops();
+        }
+        self.flush()?;
+        self.connections.retain(|_, connection| !connection.disconnected);
+        Ok(())
+    }
+
+    pub fn now(&self) -> u64 {
+        self.clock.now()
+    }
+
+    pub fn flush(&mut self) -> Result<(), RakError> {
+        for connection in self.connections.values_mut() {
+            while let Some(datagram) = connection.framer.poll_datagram() {
+                self.socket.send_to(datagram, connection.address)?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn send(&mut self, address: &SocketAddr, data: Vec<u8>) -> Result<(), RakError> {
+        match self.connections.get_mut(address) {
+            Some(connection) => {
+                connection.framer.current_time = self.clock.now();
+                connection.frame_and_send(data)?;
+                self.flush()
+            }
+            None => Err(RakError::UnknownConnection(*address))
+        }
+    }
+
+    pub fn emit_event(&self, event: ServerEvent) {
+        self.event_sender.send(event).unwrap();
+    }
+
+    pub fn handle_packet(&mut self, binary: &[u8], address: SocketAddr) -> Result<(), RakError> {
+        if binary.is_empty() {
+            return Ok(());
+        }
+        let mut packet_id = binary[0];
+        if (packet_id & 0xf0) == 0x80 {
+            packet_id = FrameSet::ID;
+        }
+
+        match packet_id {
+            UnconnectedPing::ID | 0x02 => {
+                let packet = UnconnectedPing::deserialize(binary)?;
+                let pong = UnconnectedPong::new(
+                    packet.timestamp as u64,
+                    self.guid as u64,
+                    self.message.clone()
+                );
+                self.socket.send_to(pong.serialize(), address)?;
+            }
+            ConnectionRequestOne::ID => {
+                let packet = ConnectionRequestOne::deserialize(binary.to_vec())?;
+                let mtu_size = packet.mtu_size.min(self.max_mtu_size).max(MIN_MTU_SIZE);
+                let reply = ConnectionReplyOne::new(self.guid, false, None, mtu_size);
+                self.socket.send_to(reply.serialize(), address)?;
+            }
+            ConnectionRequestTwo::ID => {
+                let packet = ConnectionRequestTwo::deserialize(binary.to_vec())?;
+                if let Some(connection) = self.connections.get(&address) {
+                    // A repeat of the request must not reset a live session: answer
+                    // again if our reply may have been lost, otherwise turn it away.
+                    let response = if connection.connected {
+                        AlreadyConnected::new(self.guid).serialize()
+                    } else {
+                        ConnectionReplyTwo::new(
+                            self.guid,
+                            Address::from_socket_address(&address),
+                            connection.framer.mtu_size,
+                            false
+                        ).serialize()
+                    };
+                    self.socket.send_to(response, address)?;
+                    return Ok(());
+                }
+                if self.connections.len() >= self.max_connections {
+                    self.socket.send_to(NoFreeIncomingConnections::new(self.guid).serialize(), address)?;
+                    return Ok(());
+                }
+                let mtu_size = packet.mtu_size.min(self.max_mtu_size).max(MIN_MTU_SIZE);
+                let reply = ConnectionReplyTwo::new(
+                    self.guid,
+                    Address::from_socket_address(&address),
+                    mtu_size,
+                    false
+                );
+                self.socket.send_to(reply.serialize(), address)?;
+
+                let connection = Connection::new(address, mtu_size, self.event_sender.clone(), self.now());
+                self.connections.insert(address, connection);
+                self.emit_event(ServerEvent::Connect { address, mtu: mtu_size, client_guid: packet.guid });
+            }
+            FrameSet::ID | Ack::ID | Nack::ID => {
+                let now = self.now();
+                let Some(connection) = self.connections.get_mut(&address) else {
+                    return Ok(());
+                };
+                connection.receive(binary, now)?;
+                self.flush()?;
+            }
+            _ => {
+                self.emit_event(ServerEvent::UnknownPacket { address, id: packet_id, payload: binary.to_vec() });
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,112 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// How badly a simulated link treats the datagrams crossing it. Probabilities
+/// are in `0.0..=1.0`, times in milliseconds.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LinkConditions {
+    pub loss: f64,
+    pub duplication: f64,
+    /// Chance that a datagram is held back by up to another `latency` so that
+    /// later ones overtake it.
+    pub reorder: f64,
+    pub latency: u64,
+    pub jitter: u64,
+    /// Bytes per second the link can carry; `None` for no cap.
+    pub bandwidth: Option<u64>,
+}
+
+impl LinkConditions {
+    pub fn lossy(loss: f64) -> Self {
+        Self { loss, ..Self::default() }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct InFlight {
+    deliver_at: u64,
+    order: u64,
+    datagram: Vec<u8>,
+}
+
+/// One direction of a simulated network path, driven by the caller's clock.
+///
+/// Everything random comes from a seeded generator, so a run with the same
+/// seed and the same traffic behaves identically.
+#[derive(Debug, Clone)]
+pub struct SimulatedLink {
+    pub conditions: LinkConditions,
+    pub rng: StdRng,
+    pub sent: u64,
+    pub dropped: u64,
+    pub duplicated: u64,
+    in_flight: Vec<InFlight>,
+    next_order: u64,
+    busy_until: u64,
+}
+
+impl SimulatedLink {
+    pub fn new(conditions: LinkConditions, seed: u64) -> Self {
+        Self {
+            conditions,
+            rng: StdRng::seed_from_u64(seed),
+            sent: 0,
+            dropped: 0,
+            duplicated: 0,
+            in_flight: Vec::new(),
+            next_order: 0,
+            busy_until: 0,
+        }
+    }
+
+    pub fn send(&mut self, datagram: Vec<u8>, now: u64) {
+        self.sent += 1;
+
+        // The bandwidth cap delays a datagram until the ones before it are on the wire.
+        let departs_at = match self.conditions.bandwidth {
+            Some(bandwidth) if bandwidth > 0 => {
+                let start = self.busy_until.max(now);
+                self.busy_until = start + (datagram.len() as u64 * 1000).div_ceil(bandwidth);
+                self.busy_until
+            }
+            _ => now,
+        };
+
+        if self.rng.gen_bool(self.conditions.loss.clamp(0.0, 1.0)) {
+            self.dropped += 1;
+            return;
+        }
+        if self.rng.gen_bool(self.conditions.duplication.clamp(0.0, 1.0)) {
+            self.duplicated += 1;
+            self.schedule(datagram.clone(), departs_at);
+        }
+        self.schedule(datagram, departs_at);
+    }
+
+    fn schedule(&mut self, datagram: Vec<u8>, departs_at: u64) {
+        let conditions = self.conditions;
+        let mut deliver_at = departs_at + conditions.latency;
+        if conditions.jitter > 0 {
+            deliver_at += self.rng.gen_range(0..=conditions.jitter);
+        }
+        if self.rng.gen_bool(conditions.reorder.clamp(0.0, 1.0)) {
+            deliver_at += self.rng.gen_range(1..=conditions.latency.max(1));
+        }
+        self.in_flight.push(InFlight { deliver_at, order: self.next_order, datagram });
+        self.next_order += 1;
+    }
+
+    /// The next datagram due by `now`, earliest first.
+    pub fn poll(&mut self, now: u64) -> Option<Vec<u8>> {
+        let index = self.in_flight.iter()
+            .enumerate()
+            .filter(|(_, packet)| packet.deliver_at <= now)
+            .min_by_key(|(_, packet)| (packet.deliver_at, packet.order))
+            .map(|(index, _)| index)?;
+        Some(self.in_flight.swap_remove(index).datagram)
+    }
+
+    pub fn is_idle(&self) -> bool {
+        self.in_flight.is_empty()
+    }
+}
@@ -0,0 +1,5 @@
+mod link;
+mod simulation;
+
+pub use link::*;
+pub use simulation::*;
@@ -1,2 +1,6 @@
+#[allow(clippy::module_inception)]
 mod socket;
+mod transport;
+
 pub use socket::*;
+pub use transport::*;
@@ -8,19 +8,40 @@ pub struct Socket {
 }
 
 impl Socket { 
-    pub fn new(server_address: String, server_port: u16) -> Self {
-        let socket = UdpSocket::bind("0.0.0.0:0").unwrap();
-        socket.connect(format!("{}:{}", server_address, server_port)).unwrap();
+    pub fn new(server_address: String, server_port: u16) -> Result<Self, std::io::Error> {
+        Self::connect("0.0.0.0:0", server_address, server_port)
+    }
+
+    /// Binds to `bind_address` and connects to the server from there.
+    pub fn connect(bind_address: &str, server_address: String, server_port: u16) -> Result<Self, std::io::Error> {
+        let socket = UdpSocket::bind(bind_address)?;
+        socket.connect(format!("{}:{}", server_address, server_port))?;
         
-        Self { 
+        Ok(Self { 
             socket,
             server_address,
             server_port,
-        }
+        })
     }
 
-    pub fn send(&self, data: Vec<u8>) -> Result<usize, std::io::Error> {
-        self.socket.send(&data)
+    pub fn bind(address: String, port: u16) -> Result<Self, std::io::Error> {
+        let socket = UdpSocket::bind(format!("{}:{}", address, port))?;
+
+        Ok(Self {
+            socket,
+            server_address: address,
+            server_port: port,
+        })
+    }
+
+    pub fn send(&self, data: &[u8]) -> Result<usize, std::io::Error> {
+        match self.socket.send(data) {
+            Ok(size) => Ok(size),
+            // A connected UDP socket reports an earlier ICMP port unreachable on the
+            // next call. Datagrams are fire and forget, so let the handshake retry instead.
+            Err(e) if e.kind() == std::io::ErrorKind::ConnectionRefused => Ok(0),
+            Err(e) => Err(e)
+        }
     }
 
     pub fn receive(&self, buffer: &mut [u8]) -> Result<usize, std::io::Error> {
@@ -28,12 +49,26 @@ impl Socket {
         match self.socket.recv(buffer) {
             Ok(size) => Ok(size),
             Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => Ok(0),
+            Err(e) if e.kind() == std::io::ErrorKind::ConnectionRefused => Ok(0),
+            Err(e) => Err(e)
+        }
+    }
+
+    pub fn send_to(&self, data: Vec<u8>, address: SocketAddr) -> Result<usize, std::io::Error> {
+        self.socket.send_to(&data, address)
+    }
+
+    pub fn receive_from(&self, buffer: &mut [u8]) -> Result<Option<(usize, SocketAddr)>, std::io::Error> {
+        self.socket.set_nonblocking(true)?;
+        match self.socket.recv_from(buffer) {
+            Ok((size, address)) => Ok(Some((size, address))),
+            Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => Ok(None),
             Err(e) => Err(e)
         }
     }
 
-    pub fn get_address(&self) -> SocketAddr {
-        self.socket.local_addr().unwrap()
+    pub fn get_address(&self) -> Result<SocketAddr, std::io::Error> {
+        self.socket.local_addr()
     }
 }
 
@@ -0,0 +1,39 @@
+use std::io;
+use std::net::SocketAddr;
+
+use super::Socket;
+
+/// Where a client's datagrams go. `Socket` is the UDP implementation; anything
+/// else (an in-memory pair, a recorder, a tunnel) only has to move bytes.
+pub trait Transport {
+    fn send(&self, datagram: &[u8]) -> io::Result<usize>;
+
+    /// Returns the size of the datagram read into `buffer`, or `None` if nothing
+    /// is waiting. Must not block.
+    fn try_receive(&self, buffer: &mut [u8]) -> io::Result<Option<usize>>;
+
+    fn local_address(&self) -> io::Result<SocketAddr>;
+
+    fn peer_address(&self) -> io::Result<SocketAddr>;
+}
+
+impl Transport for Socket {
+    fn send(&self, datagram: &[u8]) -> io::Result<usize> {
+        Socket::send(self, datagram)
+    }
+
+    fn try_receive(&self, buffer: &mut [u8]) -> io::Result<Option<usize>> {
+        match self.receive(buffer)? {
+            0 => Ok(None),
+            size => Ok(Some(size)),
+        }
+    }
+
+    fn local_address(&self) -> io::Result<SocketAddr> {
+        self.get_address()
+    }
+
+    fn peer_address(&self) -> io::Result<SocketAddr> {
+        self.socket.peer_addr()
+    }
+}
@@ -0,0 +1,81 @@
+mod common;
+
+use common::SilentPeer;
+use sanctumterra_raknet::*;
+
+fn events<T: Transport>(client: &Client<T>) -> Vec<ClientEvent> {
+    client.event_receiver.try_iter().collect()
+}
+
+#[test]
+fn mock_clock_drives_handshake_retries() {
+    let clock = MockClock::new(1_000);
+    let mut client = Client::with_transport(SilentPeer::default(), ClientConfig::default()).unwrap();
+    client.clock = Box::new(clock.clone());
+    client.connect().unwrap();
+    let requests = |client: &Client<SilentPeer>| client.transport.sent.borrow()
+        .iter()
+        .filter(|datagram| datagram[0] == ConnectionRequestOne::ID)
+        .count();
+    assert_eq!(requests(&client), 1);
+
+    clock.advance(client.config.handshake_retry_interval - 1);
+    client.tick().unwrap();
+    assert_eq!(requests(&client), 1);
+
+    clock.advance(1);
+    client.tick().unwrap();
+    assert_eq!(requests(&client), 2);
+
+    let results: Vec<Result<(), RakError>> = (0..client.max_handshake_attempts())
+        .map(|_| {
+            clock.advance(client.config.handshake_retry_interval);
+            client.tick()
+        })
+        .collect();
+    let errors: Vec<&RakError> = results.iter().filter_map(|result| result.as_ref().err()).collect();
+    assert!(matches!(errors[..], [RakError::Timeout(ConnectionState::OpenRequest1Sent)]));
+    let failed: Vec<ClientEvent> = events(&client).into_iter()
+        .filter(|event| matches!(event, ClientEvent::ConnectFailed { .. }))
+        .collect();
+    assert_eq!(failed.len(), 1);
+    assert_eq!(client.state, ConnectionState::Disconnected);
+}
+
+#[test]
+fn packet_timestamps_are_clock_milliseconds() {
+    let clock = MockClock::new(123_456);
+    let mut client = Client::with_transport(SilentPeer::default(), ClientConfig::default()).unwrap();
+    client.clock = Box::new(clock);
+    client.ping().unwrap();
+
+    let sent = client.transport.sent.borrow();
+    let ping = UnconnectedPing::deserialize(&sent[0]).unwrap();
+    assert_eq!(ping.timestamp, 123_456);
+}
+
+#[test]
+fn connection_request_retries_do_not_queue_new_requests() {
+    let clock = MockClock::new(1_000);
+    let mut client = Client::with_transport(SilentPeer::default(), ClientConfig::default()).unwrap();
+    client.clock = Box::new(clock.clone());
+    client.set_state(ConnectionState::OpenRequest2Sent);
+    let address = Address::from_socket_address(&"127.0.0.1:50000".parse().unwrap());
+    client.handle_packet(&ConnectionReplyTwo::new(1, address, 1400, false).serialize()).unwrap();
+    assert_eq!(client.state, ConnectionState::ConnectionRequestSent);
+
+    for _ in 1..client.max_handshake_attempts() {
+        clock.advance(client.config.handshake_retry_interval);
+        client.tick().unwrap();
+    }
+
+    let mut indices: Vec<Option<u32>> = client.transport.sent.borrow()
+        .iter()
+        .filter(|datagram| datagram[0] & 0xf0 == 0x80)
+        .flat_map(|datagram| FrameSet::deserialize(datagram).unwrap().frames)
+        .filter(|frame| frame.payload[0] == ConnectionRequest::ID)
+        .map(|frame| frame.reliable_frame_index)
+        .collect();
+    indices.dedup();
+    assert_eq!(indices, vec![Some(0)]);
+}
@@ -0,0 +1,30 @@
+use std::cell::RefCell;
+use std::io;
+use std::net::SocketAddr;
+
+use sanctumterra_raknet::Transport;
+
+/// A peer that never answers; it only records what the client sends.
+#[derive(Default)]
+pub struct SilentPeer {
+    pub sent: RefCell<Vec<Vec<u8>>>,
+}
+
+impl Transport for SilentPeer {
+    fn send(&self, datagram: &[u8]) -> io::Result<usize> {
+        self.sent.borrow_mut().push(datagram.to_vec());
+        Ok(datagram.len())
+    }
+
+    fn try_receive(&self, _buffer: &mut [u8]) -> io::Result<Option<usize>> {
+        Ok(None)
+    }
+
+    fn local_address(&self) -> io::Result<SocketAddr> {
+        Ok("127.0.0.1:50000".parse().unwrap())
+    }
+
+    fn peer_address(&self) -> io::Result<SocketAddr> {
+        Ok("127.0.0.1:19132".parse().unwrap())
+    }
+}
@@ -0,0 +1,19 @@
+use sanctumterra_raknet::*;
+
+fn build(config: ClientConfig) -> Result<Client, RakError> {
+    Client::with_config("127.0.0.1".to_string(), 19132, config.bind_address("127.0.0.1:0"))
+}
+
+#[test]
+fn mtu_limits_are_validated_before_connecting() {
+    let reversed = ClientConfig::default().mtu_sizes(vec![1200]).mtu_limits(1400, 1000);
+    assert!(matches!(build(reversed), Err(RakError::InvalidMtuLimits { min: 1400, max: 1000 })));
+
+    let too_small = ClientConfig::default().mtu_sizes(vec![500]).mtu_limits(30, 1000);
+    assert!(matches!(build(too_small), Err(RakError::InvalidMtuLimits { min: 30, .. })));
+
+    let outside = ClientConfig::default().mtu_sizes(vec![1492, 1200]).mtu_limits(MIN_MTU_SIZE, 1400);
+    assert!(matches!(build(outside), Err(RakError::InvalidMtuSize(1492))));
+
+    assert!(build(ClientConfig::default()).is_ok());
+}
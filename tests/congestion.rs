@@ -0,0 +1,71 @@
+use sanctumterra_raknet::*;
+
+const MTU: u16 = 1400;
+const MTU_BYTES: usize = MTU as usize;
+
+/// Reports `count` datagrams as sent, starting at sequence `first`.
+fn send(window: &mut SlidingWindow, first: u32, count: u32) {
+    for sequence in first..first + count {
+        window.on_send(sequence, MTU_BYTES);
+    }
+}
+
+#[test]
+fn slow_start_grows_by_one_mtu_per_ack() {
+    let mut window = SlidingWindow::new(MTU);
+    assert_eq!(window.window(), MTU_BYTES);
+    send(&mut window, 0, 4);
+    for sequence in 0..4 {
+        window.on_ack(sequence, MTU_BYTES);
+    }
+
+    assert!(window.is_slow_start());
+    assert_eq!(window.window(), 5 * MTU_BYTES);
+}
+
+#[test]
+fn nack_halves_the_window_once_per_flight() {
+    let mut window = SlidingWindow::new(MTU);
+    window.congestion_window = (8 * MTU_BYTES) as f64;
+    send(&mut window, 0, 8);
+
+    window.on_nack(2);
+    assert_eq!(window.window(), 4 * MTU_BYTES);
+    window.on_nack(5);
+    assert_eq!(window.window(), 4 * MTU_BYTES, "loss from the same flight backs off only once");
+
+    send(&mut window, 8, 4);
+    window.on_nack(9);
+    assert_eq!(window.window(), 2 * MTU_BYTES);
+}
+
+#[test]
+fn congestion_avoidance_grows_by_a_fraction_per_ack() {
+    let mut window = SlidingWindow::new(MTU);
+    window.congestion_window = (8 * MTU_BYTES) as f64;
+    send(&mut window, 0, 8);
+    window.on_nack(0);
+    window.on_ack(1, MTU_BYTES);
+    assert!(!window.is_slow_start());
+
+    let before = window.congestion_window;
+    window.on_ack(2, MTU_BYTES);
+    let growth = window.congestion_window - before;
+    assert!((growth - (MTU_BYTES * MTU_BYTES) as f64 / before).abs() < 1e-9);
+    assert!(window.window() < 5 * MTU_BYTES);
+}
+
+#[test]
+fn timeout_collapses_to_one_mtu_and_restarts_slow_start() {
+    let mut window = SlidingWindow::new(MTU);
+    window.congestion_window = (8 * MTU_BYTES) as f64;
+    send(&mut window, 0, 8);
+
+    window.on_timeout(3);
+    assert_eq!(window.window(), MTU_BYTES);
+    assert_eq!(window.slow_start_threshold, (4 * MTU_BYTES) as f64);
+    assert!(window.is_slow_start());
+
+    window.on_ack(4, MTU_BYTES);
+    assert_eq!(window.window(), 2 * MTU_BYTES);
+}
@@ -0,0 +1,47 @@
+use sanctumterra_raknet::*;
+
+fn send(framer: &mut Framer, reliability: Reliability, payload: Vec<u8>) {
+    let mut frame = Frame::new();
+    frame.reliability = reliability;
+    frame.payload = payload;
+    framer.send_frame(&mut frame, Some(Priority::Immediate)).unwrap();
+}
+
+fn messages(framer: &mut Framer) -> usize {
+    std::iter::from_fn(|| framer.poll_message()).count()
+}
+
+#[test]
+fn repeated_datagram_is_delivered_once_across_ticks() {
+    for reliability in [Reliability::Unreliable, Reliability::UnreliableWithAckReceipt, Reliability::Reliable] {
+        let mut sender = Framer::new(1400);
+        let mut receiver = Framer::new(1400);
+        send(&mut sender, reliability, vec![1, 2, 3]);
+        let datagram = sender.poll_datagram().unwrap();
+
+        receiver.receive(&datagram, 0).unwrap();
+        receiver.tick(10);
+        let first_ack = receiver.poll_datagram().unwrap();
+        receiver.receive(&datagram, 20).unwrap();
+        receiver.tick(30);
+
+        assert_eq!(messages(&mut receiver), 1, "{:?}", reliability);
+        assert_eq!(first_ack[0], Ack::ID);
+        assert_eq!(receiver.poll_datagram().map(|ack| ack[0]), Some(Ack::ID), "duplicate is ACKed again");
+    }
+}
+
+#[test]
+fn datagram_window_slides_past_gaps_that_never_fill() {
+    let mut window = ReliableWindow::new();
+    assert!(window.receive_sliding(1));
+    assert!(window.receive_sliding(RELIABLE_WINDOW_SIZE + 10));
+    assert!(!window.receive_sliding(RELIABLE_WINDOW_SIZE + 10));
+    assert!(!window.receive_sliding(1));
+    assert!(window.receive_sliding(RELIABLE_WINDOW_SIZE + 9));
+
+    let mut window = ReliableWindow { base: U24_MASK - 5, ..ReliableWindow::default() };
+    assert!(window.receive_sliding(RELIABLE_WINDOW_SIZE));
+    assert!(window.receive_sliding(1));
+    assert!(!window.receive_sliding(U24_MASK - 5));
+}
@@ -0,0 +1,114 @@
+use sanctumterra_raknet::*;
+
+fn send(framer: &mut Framer, reliability: Reliability, payload: Vec<u8>) {
+    let mut frame = Frame::new();
+    frame.reliability = reliability;
+    frame.payload = payload;
+    framer.send_frame(&mut frame, Some(Priority::Immediate)).unwrap();
+}
+
+/// A sender whose window never holds anything back, so every send is its own datagram.
+fn unthrottled(mtu_size: u16) -> Framer {
+    let mut framer = Framer::new(mtu_size);
+    framer.congestion = Box::new(FixedWindow::new(usize::MAX));
+    framer
+}
+
+fn datagrams(framer: &mut Framer) -> Vec<Vec<u8>> {
+    std::iter::from_fn(|| framer.poll_datagram()).collect()
+}
+
+fn payloads(framer: &mut Framer) -> Vec<Vec<u8>> {
+    std::iter::from_fn(|| framer.poll_message()).map(|frame| frame.payload).collect()
+}
+
+#[test]
+fn ack_releases_the_backup() {
+    let mut sender = Framer::new(1400);
+    let mut receiver = Framer::new(1400);
+    send(&mut sender, Reliability::Reliable, vec![1, 2, 3]);
+    let datagram = sender.poll_datagram().unwrap();
+    assert_eq!(sender.output_backup.len(), 1);
+
+    receiver.receive(&datagram, 0).unwrap();
+    receiver.tick(10);
+    for ack in datagrams(&mut receiver) {
+        sender.receive(&ack, 20).unwrap();
+    }
+
+    assert!(sender.output_backup.is_empty());
+    assert_eq!(sender.bytes_in_flight, 0);
+    assert_eq!(sender.stats.acks_received, 1);
+    assert_eq!(sender.rtt.samples, 1);
+}
+
+#[test]
+fn nack_resends_under_a_new_sequence() {
+    let mut sender = Framer::new(1400);
+    send(&mut sender, Reliability::Reliable, vec![1, 2, 3]);
+    let lost = FrameSet::deserialize(&sender.poll_datagram().unwrap()).unwrap();
+
+    sender.receive(&Nack::new(vec![lost.sequence]).serialize(), 10).unwrap();
+
+    let resent = FrameSet::deserialize(&sender.poll_datagram().unwrap()).unwrap();
+    assert_ne!(resent.sequence, lost.sequence);
+    assert_eq!(resent.frames[0].payload, vec![1, 2, 3]);
+    assert_eq!(resent.frames[0].reliable_frame_index, lost.frames[0].reliable_frame_index);
+    assert_eq!(sender.stats.datagrams_resent, 1);
+}
+
+#[test]
+fn sequence_gap_is_nacked_and_the_rest_acked() {
+    let mut sender = unthrottled(1400);
+    let mut receiver = Framer::new(1400);
+    for index in 0..3u8 {
+        send(&mut sender, Reliability::Reliable, vec![index]);
+    }
+    let sent = datagrams(&mut sender);
+    assert_eq!(sent.len(), 3);
+
+    receiver.receive(&sent[0], 0).unwrap();
+    receiver.receive(&sent[2], 0).unwrap();
+    receiver.tick(10);
+
+    let replies = datagrams(&mut receiver);
+    let mut acked = Ack::deserialize(&replies[0]).unwrap().sequences;
+    acked.sort();
+    assert_eq!(acked, vec![0, 2]);
+    assert_eq!(Nack::deserialize(&replies[1]).unwrap().sequences, vec![1]);
+}
+
+#[test]
+fn split_frames_reassemble_in_any_order() {
+    let mut sender = unthrottled(MIN_MTU_SIZE);
+    let mut receiver = Framer::new(MIN_MTU_SIZE);
+    let payload: Vec<u8> = (0..3000u32).map(|byte| byte as u8).collect();
+    send(&mut sender, Reliability::ReliableOrdered, payload.clone());
+    let sent = datagrams(&mut sender);
+    assert!(sent.len() > 1);
+
+    for datagram in sent.iter().rev() {
+        assert!(payloads(&mut receiver).is_empty());
+        receiver.receive(datagram, 0).unwrap();
+    }
+
+    assert_eq!(payloads(&mut receiver), vec![payload]);
+    assert!(receiver.fragments_queue.is_empty());
+}
+
+#[test]
+fn ordered_frames_wait_for_the_missing_index() {
+    let mut sender = unthrottled(1400);
+    let mut receiver = Framer::new(1400);
+    for index in 0..3u8 {
+        send(&mut sender, Reliability::ReliableOrdered, vec![index]);
+    }
+    let sent = datagrams(&mut sender);
+
+    receiver.receive(&sent[2], 0).unwrap();
+    receiver.receive(&sent[1], 0).unwrap();
+    assert!(payloads(&mut receiver).is_empty());
+
+    receiver.receive(&sent[0], 0).unwrap();
+    assert_eq!(payloads(&mut receiver), vec![vec![0], vec![1], vec![2]]);
+}
@@ -0,0 +1,29 @@
+use sanctumterra_raknet::*;
+
+#[test]
+fn unconnected_pong_puts_the_guid_before_the_magic() {
+    let pong = UnconnectedPong::new(1234, 0x0102030405060708, "MCPE;motd".to_string());
+    let bytes = pong.serialize();
+
+    assert_eq!(&bytes[9..17], &0x0102030405060708u64.to_be_bytes());
+    assert_eq!(&bytes[17..33], &MAGIC[..]);
+    let decoded = UnconnectedPong::deserialize(bytes).unwrap();
+    assert_eq!((decoded.timestamp, decoded.guid, decoded.message.as_str()), (1234, 0x0102030405060708, "MCPE;motd"));
+}
+
+#[test]
+fn ack_ranges_are_capped_before_expanding() {
+    let mut hostile = vec![Ack::ID];
+    hostile.extend_from_slice(&200u16.to_be_bytes());
+    for _ in 0..200 {
+        hostile.extend_from_slice(&[0, 0, 0, 0, 0xff, 0xff, 0xff]);
+    }
+    let error = Ack::deserialize(&hostile).unwrap_err();
+    assert!(matches!(error, BinaryError::TooLarge { field: "sequences", .. }));
+
+    hostile[0] = Nack::ID;
+    assert!(Nack::deserialize(&hostile).is_err());
+
+    let ack = Ack::deserialize(&Ack::new((0..1000).collect()).serialize()).unwrap();
+    assert_eq!(ack.sequences, (0..1000).collect::<Vec<u32>>());
+}
@@ -0,0 +1,85 @@
+use sanctumterra_raknet::*;
+
+fn send(framer: &mut Framer, reliability: Reliability, payload: Vec<u8>) -> u32 {
+    let mut frame = Frame::new();
+    frame.reliability = reliability;
+    frame.payload = payload;
+    framer.send_frame(&mut frame, Some(Priority::Immediate)).unwrap()
+}
+
+fn datagrams(framer: &mut Framer) -> Vec<Vec<u8>> {
+    std::iter::from_fn(|| framer.poll_datagram()).collect()
+}
+
+fn receipts(framer: &mut Framer) -> Vec<Receipt> {
+    std::iter::from_fn(|| framer.poll_receipt()).collect()
+}
+
+fn sequence(datagram: &[u8]) -> u32 {
+    FrameSet::deserialize(datagram).unwrap().sequence
+}
+
+#[test]
+fn acked_datagram_settles_its_receipt() {
+    let mut sender = Framer::new(1400);
+    let receipt = send(&mut sender, Reliability::ReliableWithAckReceipt, vec![1]);
+    let datagram = sender.poll_datagram().unwrap();
+    assert!(receipts(&mut sender).is_empty());
+
+    sender.receive(&Ack::new(vec![sequence(&datagram)]).serialize(), 10).unwrap();
+
+    assert_eq!(receipts(&mut sender), vec![Receipt::Acked(receipt)]);
+}
+
+#[test]
+fn nacked_unreliable_frame_is_reported_lost() {
+    let mut sender = Framer::new(1400);
+    let receipt = send(&mut sender, Reliability::UnreliableWithAckReceipt, vec![1]);
+    let datagram = sender.poll_datagram().unwrap();
+
+    sender.receive(&Nack::new(vec![sequence(&datagram)]).serialize(), 10).unwrap();
+
+    assert_eq!(receipts(&mut sender), vec![Receipt::Lost(receipt)]);
+}
+
+#[test]
+fn unacknowledged_unreliable_frame_is_lost_after_the_timeout() {
+    let mut sender = Framer::new(1400);
+    let receipt = send(&mut sender, Reliability::UnreliableWithAckReceipt, vec![1]);
+    sender.poll_datagram();
+
+    sender.tick(INITIAL_RETRANSMISSION_TIMEOUT);
+    assert!(receipts(&mut sender).is_empty());
+    sender.tick(INITIAL_RETRANSMISSION_TIMEOUT + 1);
+    assert_eq!(receipts(&mut sender), vec![Receipt::Lost(receipt)]);
+}
+
+#[test]
+fn nacked_reliable_frame_is_resent_and_acked_later() {
+    let mut sender = Framer::new(1400);
+    let receipt = send(&mut sender, Reliability::ReliableWithAckReceipt, vec![1]);
+    let lost = sender.poll_datagram().unwrap();
+
+    sender.receive(&Nack::new(vec![sequence(&lost)]).serialize(), 10).unwrap();
+    assert!(receipts(&mut sender).is_empty());
+    let resent = sender.poll_datagram().unwrap();
+    sender.receive(&Ack::new(vec![sequence(&resent)]).serialize(), 20).unwrap();
+
+    assert_eq!(receipts(&mut sender), vec![Receipt::Acked(receipt)]);
+}
+
+#[test]
+fn split_receipt_waits_for_every_fragment() {
+    let mut sender = Framer::new(MIN_MTU_SIZE);
+    sender.congestion = Box::new(FixedWindow::new(usize::MAX));
+    let receipt = send(&mut sender, Reliability::ReliableWithAckReceipt, vec![7; 1000]);
+    let sent: Vec<u32> = datagrams(&mut sender).iter().map(|datagram| sequence(datagram)).collect();
+    assert!(sent.len() > 1);
+
+    let (last, rest) = sent.split_last().unwrap();
+    sender.receive(&Ack::new(rest.to_vec()).serialize(), 10).unwrap();
+    assert!(receipts(&mut sender).is_empty());
+
+    sender.receive(&Ack::new(vec![*last]).serialize(), 20).unwrap();
+    assert_eq!(receipts(&mut sender), vec![Receipt::Acked(receipt)]);
+}
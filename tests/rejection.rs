@@ -0,0 +1,72 @@
+mod common;
+
+use common::SilentPeer;
+use sanctumterra_raknet::*;
+
+fn failures<T: Transport>(client: &Client<T>) -> Vec<ConnectFailedReason> {
+    client.event_receiver.try_iter()
+        .filter_map(|event| match event {
+            ClientEvent::ConnectFailed { reason } => Some(reason),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn rejections_fail_the_handshake_with_a_reason() {
+    let rejections = [
+        (ConnectionBanned::new(1).serialize(), ConnectFailedReason::Banned),
+        (NoFreeIncomingConnections::new(1).serialize(), ConnectFailedReason::NoFreeIncomingConnections),
+        (AlreadyConnected::new(1).serialize(), ConnectFailedReason::AlreadyConnected),
+        (ConnectionAttemptFailed::new(1).serialize(), ConnectFailedReason::ConnectionAttemptFailed),
+        (IpRecentlyConnected::new(1).serialize(), ConnectFailedReason::IpRecentlyConnected),
+    ];
+    for (packet, reason) in rejections {
+        let mut client = Client::with_transport(SilentPeer::default(), ClientConfig::default()).unwrap();
+        client.connect().unwrap();
+        let result = client.handle_packet(&packet);
+
+        assert!(matches!(result, Err(RakError::ConnectionRejected(rejected)) if rejected == reason));
+        assert_eq!(client.state, ConnectionState::Disconnected);
+        assert_eq!(client.connect_failure, Some(reason));
+        assert_eq!(failures(&client), vec![reason]);
+    }
+}
+
+#[test]
+fn rejections_are_ignored_outside_the_handshake() {
+    let mut client = Client::with_transport(SilentPeer::default(), ClientConfig::default()).unwrap();
+    client.handle_packet(&ConnectionBanned::new(1).serialize()).unwrap();
+
+    assert_eq!(client.state, ConnectionState::Unconnected);
+    assert_eq!(client.connect_failure, None);
+}
+
+#[test]
+fn protocol_mismatch_retries_with_the_server_version() {
+    let mut client = Client::with_transport(SilentPeer::default(), ClientConfig::default().protocol(11)).unwrap();
+    client.connect().unwrap();
+    client.handle_packet(&IncompatibleProtocolVersion::new(10, 1).serialize()).unwrap();
+
+    assert_eq!(client.config.protocol, 10);
+    assert_eq!(client.state, ConnectionState::OpenRequest1Sent);
+    let sent = client.transport.sent.borrow();
+    let request = ConnectionRequestOne::deserialize(sent.last().unwrap().clone()).unwrap();
+    assert_eq!(request.protocol, 10);
+    drop(sent);
+
+    client.handle_packet(&IncompatibleProtocolVersion::new(9, 1).serialize()).unwrap_err();
+    assert_eq!(client.state, ConnectionState::Disconnected);
+    assert_eq!(client.connect_failure, Some(ConnectFailedReason::IncompatibleProtocol { server_protocol: 9 }));
+}
+
+#[test]
+fn protocol_mismatch_fails_when_adoption_is_disabled() {
+    let config = ClientConfig::default().adopt_server_protocol(false);
+    let mut client = Client::with_transport(SilentPeer::default(), config).unwrap();
+    client.connect().unwrap();
+    client.handle_packet(&IncompatibleProtocolVersion::new(10, 1).serialize()).unwrap_err();
+
+    assert_eq!(client.state, ConnectionState::Disconnected);
+    assert_eq!(failures(&client), vec![ConnectFailedReason::IncompatibleProtocol { server_protocol: 10 }]);
+}
@@ -0,0 +1,55 @@
+use sanctumterra_raknet::*;
+
+fn frame(tag: u8) -> Frame {
+    let mut frame = Frame::new();
+    frame.payload = vec![tag];
+    frame
+}
+
+#[test]
+fn low_priority_is_not_starved_by_high() {
+    let mut queue = SendQueue::new();
+    for _ in 0..1000 {
+        queue.push(frame(1), Priority::High);
+    }
+    for _ in 0..100 {
+        queue.push(frame(3), Priority::Low);
+    }
+
+    let low = (0..80)
+        .filter_map(|_| queue.pop())
+        .filter(|frame| frame.payload[0] == 3)
+        .count();
+    assert!((5..=15).contains(&low), "{} low frames in the first 80", low);
+}
+
+#[test]
+fn immediate_goes_before_queued_low() {
+    let mut queue = SendQueue::new();
+    queue.push(frame(3), Priority::Low);
+    queue.push(frame(3), Priority::Low);
+    queue.push(frame(0), Priority::Immediate);
+
+    assert_eq!(queue.pop().unwrap().payload, vec![0]);
+}
+
+#[test]
+fn idle_level_does_not_bank_credit() {
+    let mut queue = SendQueue::new();
+    queue.push(frame(3), Priority::Low);
+    for _ in 0..200 {
+        queue.push(frame(1), Priority::High);
+    }
+    for _ in 0..100 {
+        queue.pop();
+    }
+    for _ in 0..100 {
+        queue.push(frame(2), Priority::Medium);
+    }
+
+    let medium = (0..10)
+        .filter_map(|_| queue.pop())
+        .filter(|frame| frame.payload[0] == 2)
+        .count();
+    assert!(medium < 10, "a level that was idle must not monopolise the queue");
+}
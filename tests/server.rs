@@ -0,0 +1,65 @@
+use std::net::SocketAddr;
+
+use sanctumterra_raknet::*;
+
+fn server(clock: &MockClock) -> Server {
+    let mut server = Server::new("127.0.0.1".into(), 0).unwrap();
+    server.clock = Box::new(clock.clone());
+    server
+}
+
+fn open(server: &mut Server, address: SocketAddr) {
+    let request = ConnectionRequestTwo::new(Address::from_socket_address(&address), 1400, 1);
+    server.handle_packet(&request.serialize(), address).unwrap();
+}
+
+fn events(server: &Server) -> Vec<ServerEvent> {
+    server.event_receiver.try_iter().collect()
+}
+
+#[test]
+fn repeated_open_request_keeps_the_live_session() {
+    let clock = MockClock::new(1_000);
+    let mut server = server(&clock);
+    let address: SocketAddr = "127.0.0.1:40001".parse().unwrap();
+    open(&mut server, address);
+    server.connections.get_mut(&address).unwrap().connected = true;
+
+    open(&mut server, address);
+
+    assert!(server.connections[&address].connected);
+    let connects = events(&server).into_iter()
+        .filter(|event| matches!(event, ServerEvent::Connect { .. }))
+        .count();
+    assert_eq!(connects, 1);
+}
+
+#[test]
+fn sessions_beyond_max_connections_are_refused() {
+    let clock = MockClock::new(1_000);
+    let mut server = server(&clock);
+    server.max_connections = 2;
+    for port in 40001..40004 {
+        open(&mut server, SocketAddr::from(([127, 0, 0, 1], port)));
+    }
+
+    assert_eq!(server.connections.len(), 2);
+}
+
+#[test]
+fn silent_sessions_expire() {
+    let clock = MockClock::new(1_000);
+    let mut server = server(&clock);
+    let address: SocketAddr = "127.0.0.1:40001".parse().unwrap();
+    open(&mut server, address);
+    events(&server);
+
+    clock.advance(server.session_timeout - 1);
+    server.tick().unwrap();
+    assert_eq!(server.connections.len(), 1);
+
+    clock.advance(1);
+    server.tick().unwrap();
+    assert!(server.connections.is_empty());
+    assert_eq!(events(&server), vec![ServerEvent::Disconnected { address, reason: DisconnectReason::Timeout }]);
+}
@@ -0,0 +1,74 @@
+mod common;
+
+use common::SilentPeer;
+use sanctumterra_raknet::*;
+
+/// A client that believes it finished the handshake at the clock's current time.
+fn connected(clock: &MockClock) -> Client<SilentPeer> {
+    let mut client = Client::with_transport(SilentPeer::default(), ClientConfig::default()).unwrap();
+    client.clock = Box::new(clock.clone());
+    client.set_state(ConnectionState::Connected);
+    client.last_received_at = clock.now();
+    client.last_ping_at = clock.now();
+    client
+}
+
+fn events<T: Transport>(client: &Client<T>) -> Vec<ClientEvent> {
+    client.event_receiver.try_iter().collect()
+}
+
+#[test]
+fn silent_server_times_the_session_out() {
+    let clock = MockClock::new(1_000);
+    let mut client = connected(&clock);
+
+    clock.advance(client.config.session_timeout - 1);
+    client.tick().unwrap();
+    assert!(client.is_connected());
+
+    clock.advance(1);
+    client.tick().unwrap();
+    assert_eq!(client.state, ConnectionState::Disconnected);
+    let disconnects: Vec<ClientEvent> = events(&client).into_iter()
+        .filter(|event| matches!(event, ClientEvent::Disconnected { .. }))
+        .collect();
+    assert_eq!(disconnects, vec![ClientEvent::Disconnected { reason: DisconnectReason::Timeout }]);
+}
+
+#[test]
+fn connected_pong_updates_latency() {
+    let clock = MockClock::new(1_000);
+    let mut client = connected(&clock);
+    clock.advance(client.config.ping_interval);
+    client.tick().unwrap();
+    let ping_sent_at = client.last_ping_at;
+    assert_eq!(ping_sent_at, clock.now());
+
+    clock.advance(80);
+    let mut frame = Frame::new();
+    frame.payload = ConnectedPong::new(ping_sent_at as i64, 0).serialize();
+    client.handle_message(&frame).unwrap();
+
+    assert_eq!(client.latency().rtt, 80.0);
+    assert_eq!(client.latency().last_ping, 80);
+    assert!(events(&client).contains(&ClientEvent::LatencyUpdated { rtt: 80.0 }));
+}
+
+#[test]
+fn disconnect_notifies_the_server_and_reports_client_requested() {
+    let clock = MockClock::new(1_000);
+    let mut client = connected(&clock);
+    client.config.disconnect_timeout = 20;
+
+    client.disconnect().unwrap();
+
+    assert_eq!(client.state, ConnectionState::Disconnected);
+    assert!(events(&client).contains(&ClientEvent::Disconnected { reason: DisconnectReason::ClientRequested }));
+    let notified = client.transport.sent.borrow()
+        .iter()
+        .filter(|datagram| datagram[0] & 0xf0 == 0x80)
+        .flat_map(|datagram| FrameSet::deserialize(datagram).unwrap().frames)
+        .any(|frame| frame.payload[0] == DisconnectionNotification::ID);
+    assert!(notified);
+    assert!(matches!(client.disconnect(), Err(RakError::NotConnected)));
+}
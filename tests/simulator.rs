@@ -0,0 +1,207 @@
+use sanctumterra_raknet::*;
+
+const SEED: u64 = 0x5eed;
+
+fn send(framer: &mut Framer, reliability: Reliability, channel: u8, payload: Vec<u8>) {
+    let mut frame = Frame::new();
+    frame.reliability = reliability;
+    frame.order_channel = Some(channel);
+    frame.payload = payload;
+    framer.send_frame(&mut frame, None).unwrap();
+}
+
+/// 1 MB split into messages of varying size, each tagged with its index.
+fn messages() -> Vec<Vec<u8>> {
+    let mut messages = Vec::new();
+    let mut total = 0;
+    let mut index = 0u32;
+    while total < 1024 * 1024 {
+        let size = 64 + (index as usize * 997) % 6000;
+        let mut message = index.to_le_bytes().to_vec();
+        message.extend((0..size).map(|byte| (byte as u32 ^ index) as u8));
+        total += message.len();
+        messages.push(message);
+        index += 1;
+    }
+    messages
+}
+
+fn payloads(frames: &[Frame]) -> Vec<Vec<u8>> {
+    frames.iter().map(|frame| frame.payload.clone()).collect()
+}
+
+fn first_sends(simulation: &Simulation) -> u64 {
+    simulation.local.stats.datagrams_sent - simulation.local.stats.datagrams_resent
+}
+
+#[test]
+fn one_megabyte_reliable_ordered_arrives_intact_and_in_order_at_20_percent_loss() {
+    let mut simulation = Simulation::new(1400, LinkConditions::lossy(0.2), SEED);
+    let sent = messages();
+    for message in &sent {
+        send(&mut simulation.local, Reliability::ReliableOrdered, 0, message.clone());
+    }
+
+    let delivered = simulation.run_until(600_000, |delivered| delivered.len() >= sent.len());
+
+    assert_eq!(payloads(&delivered), sent);
+    assert!(simulation.outbound.dropped > 0);
+    assert!(simulation.local.stats.datagrams_resent > 0);
+    assert!(simulation.local.stats.datagrams_resent < first_sends(&simulation));
+}
+
+#[test]
+fn reliable_frames_are_delivered_once_despite_duplication_and_reordering() {
+    let conditions = LinkConditions {
+        loss: 0.1,
+        duplication: 0.3,
+        reorder: 0.3,
+        latency: 40,
+        jitter: 20,
+        ..LinkConditions::default()
+    };
+    let mut simulation = Simulation::new(1400, conditions, SEED);
+    for index in 0..500u32 {
+        send(&mut simulation.local, Reliability::Reliable, 0, index.to_le_bytes().to_vec());
+    }
+
+    let mut delivered = simulation.run_until(120_000, |delivered| delivered.len() >= 500);
+    delivered.extend(simulation.run_until(5_000, |_| false));
+
+    let mut received: Vec<u32> = delivered.iter()
+        .map(|frame| u32::from_le_bytes(frame.payload[..4].try_into().unwrap()))
+        .collect();
+    received.sort();
+    assert_eq!(received, (0..500).collect::<Vec<_>>());
+    assert!(simulation.outbound.duplicated > 0);
+}
+
+#[test]
+fn ordering_channels_are_independent_under_jitter() {
+    let conditions = LinkConditions {
+        loss: 0.05,
+        reorder: 0.2,
+        latency: 30,
+        jitter: 30,
+        ..LinkConditions::default()
+    };
+    let mut simulation = Simulation::new(1400, conditions, SEED);
+    for index in 0..200u8 {
+        send(&mut simulation.local, Reliability::ReliableOrdered, index % 4, vec![index % 4, index]);
+    }
+
+    let delivered = simulation.run_until(120_000, |delivered| delivered.len() >= 200);
+
+    assert_eq!(delivered.len(), 200);
+    for channel in 0..4u8 {
+        let on_channel: Vec<u8> = delivered.iter()
+            .filter(|frame| frame.payload[0] == channel)
+            .map(|frame| frame.payload[1])
+            .collect();
+        let expected: Vec<u8> = (0..200u8).filter(|index| index % 4 == channel).collect();
+        assert_eq!(on_channel, expected);
+    }
+}
+
+#[test]
+fn unreliable_frames_are_lost_but_never_duplicated() {
+    // Jitter spreads a datagram and its duplicate over several ticks, so the
+    // receiver has ACKed the first copy by the time the second one lands.
+    let conditions = LinkConditions { loss: 0.3, duplication: 0.3, latency: 20, jitter: 60, ..LinkConditions::default() };
+    let mut simulation = Simulation::new(1400, conditions, SEED);
+    simulation.local.congestion = Box::new(Unlimited);
+    for index in 0..200u32 {
+        let mut payload = index.to_le_bytes().to_vec();
+        payload.resize(1000, 0);
+        send(&mut simulation.local, Reliability::Unreliable, 0, payload);
+    }
+
+    let delivered = simulation.run_until(5_000, |_| false);
+
+    let mut received: Vec<u32> = delivered.iter()
+        .map(|frame| u32::from_le_bytes(frame.payload[..4].try_into().unwrap()))
+        .collect();
+    assert!(!received.is_empty() && received.len() < 200);
+    assert!(simulation.outbound.duplicated > 0);
+    let count = received.len();
+    received.sort_unstable();
+    received.dedup();
+    assert_eq!(received.len(), count, "an unreliable frame was delivered twice");
+}
+
+#[test]
+fn bandwidth_cap_paces_delivery() {
+    let conditions = LinkConditions { latency: 20, bandwidth: Some(100 * 1024), ..LinkConditions::default() };
+    let mut simulation = Simulation::new(1400, conditions, SEED);
+    send(&mut simulation.local, Reliability::ReliableOrdered, 0, vec![7; 200 * 1024]);
+
+    let delivered = simulation.run_until(60_000, |delivered| !delivered.is_empty());
+
+    assert_eq!(payloads(&delivered), vec![vec![7; 200 * 1024]]);
+    assert!(simulation.now >= 2000, "200 KB over 100 KB/s took {} ms", simulation.now);
+}
+
+#[test]
+fn the_same_seed_gives_the_same_run() {
+    let run = || {
+        let conditions = LinkConditions { loss: 0.2, duplication: 0.1, reorder: 0.1, latency: 25, jitter: 10, bandwidth: None };
+        let mut simulation = Simulation::new(1400, conditions, SEED);
+        for index in 0..100u8 {
+            send(&mut simulation.local, Reliability::ReliableOrdered, 0, vec![index; 3000]);
+        }
+        simulation.run_until(120_000, |delivered| delivered.len() >= 100);
+        (simulation.now, simulation.outbound.dropped, simulation.local.stats.datagrams_sent)
+    };
+
+    assert_eq!(run(), run());
+}
+
+#[test]
+fn lossless_fixed_latency_link_needs_no_resends() {
+    let conditions = LinkConditions { latency: 50, ..LinkConditions::default() };
+    let mut simulation = Simulation::new(1400, conditions, SEED);
+    for index in 0..3000u32 {
+        let mut payload = index.to_le_bytes().to_vec();
+        payload.resize(1000, 0);
+        send(&mut simulation.local, Reliability::ReliableOrdered, 0, payload);
+    }
+
+    let delivered = simulation.run_until(120_000, |delivered| delivered.len() >= 3000);
+
+    assert_eq!(delivered.len(), 3000);
+    assert!(delivered.iter().enumerate().all(|(index, frame)| frame.payload[..4] == (index as u32).to_le_bytes()));
+    assert_eq!(simulation.local.stats.datagrams_resent, 0);
+}
+
+#[test]
+fn many_small_ordered_messages_drain_completely_under_loss() {
+    let conditions = LinkConditions { loss: 0.02, latency: 50, ..LinkConditions::default() };
+    let mut simulation = Simulation::new(1400, conditions, SEED);
+    for index in 0..20_000u32 {
+        send(&mut simulation.local, Reliability::ReliableOrdered, 0, index.to_le_bytes().to_vec());
+    }
+
+    let delivered = simulation.run_until(600_000, |delivered| delivered.len() >= 20_000);
+
+    let received: Vec<u32> = delivered.iter()
+        .map(|frame| u32::from_le_bytes(frame.payload[..4].try_into().unwrap()))
+        .collect();
+    assert_eq!(received, (0..20_000).collect::<Vec<_>>());
+    assert!(simulation.remote.input_ordering_queue.values().all(|queue| queue.is_empty()));
+    assert!(simulation.local.stats.datagrams_resent * 4 < first_sends(&simulation));
+}
+
+#[test]
+fn one_megabyte_over_fixed_latency_at_20_percent_loss_resends_less_than_it_sends() {
+    let conditions = LinkConditions { loss: 0.2, latency: 50, ..LinkConditions::default() };
+    let mut simulation = Simulation::new(1400, conditions, SEED);
+    let sent = messages();
+    for message in &sent {
+        send(&mut simulation.local, Reliability::ReliableOrdered, 0, message.clone());
+    }
+
+    let delivered = simulation.run_until(600_000, |delivered| delivered.len() >= sent.len());
+
+    assert_eq!(payloads(&delivered), sent);
+    assert!(simulation.local.stats.datagrams_resent < first_sends(&simulation));
+}
@@ -0,0 +1,91 @@
+mod common;
+
+use common::SilentPeer;
+use sanctumterra_raknet::*;
+
+fn fragment(split_id: u16, index: u32, size: u32, length: usize) -> Frame {
+    let mut frame = Frame::new();
+    frame.reliability = Reliability::Unreliable;
+    frame.split_id = Some(split_id);
+    frame.split_frame_index = Some(index);
+    frame.split_size = Some(size);
+    frame.payload = vec![index as u8; length];
+    frame
+}
+
+fn drops(framer: &mut Framer) -> Vec<SplitDrop> {
+    std::iter::from_fn(|| framer.poll_split_drop()).collect()
+}
+
+#[test]
+fn oversized_split_size_is_dropped_before_buffering() {
+    let mut framer = Framer::new(1400);
+    framer.handle_split_frame(&fragment(1, 0, framer.split_limits.max_fragments + 1, 10));
+
+    assert!(framer.fragments_queue.is_empty());
+    assert_eq!(drops(&mut framer), vec![SplitDrop { split_id: 1, reason: SplitDropReason::TooManyFragments }]);
+}
+
+#[test]
+fn splits_beyond_the_concurrent_limit_are_dropped() {
+    let mut framer = Framer::new(1400);
+    framer.split_limits.max_concurrent_splits = 2;
+    for split_id in 0..3 {
+        framer.handle_split_frame(&fragment(split_id, 0, 4, 10));
+    }
+
+    assert_eq!(framer.fragments_queue.len(), 2);
+    assert_eq!(drops(&mut framer), vec![SplitDrop { split_id: 2, reason: SplitDropReason::TooManySplits }]);
+}
+
+#[test]
+fn reassembly_past_the_byte_limit_is_dropped() {
+    let mut framer = Framer::new(1400);
+    framer.split_limits.max_reassembled_bytes = 25;
+    framer.handle_split_frame(&fragment(1, 0, 3, 10));
+    framer.handle_split_frame(&fragment(1, 1, 3, 10));
+    framer.handle_split_frame(&fragment(1, 2, 3, 10));
+
+    assert!(framer.fragments_queue.is_empty());
+    assert!(framer.poll_message().is_none());
+    assert_eq!(drops(&mut framer), vec![SplitDrop { split_id: 1, reason: SplitDropReason::TooLarge }]);
+}
+
+#[test]
+fn inconsistent_fragments_are_dropped() {
+    let mut framer = Framer::new(1400);
+    framer.handle_split_frame(&fragment(1, 0, 3, 10));
+    framer.handle_split_frame(&fragment(1, 1, 4, 10));
+    framer.handle_split_frame(&fragment(2, 5, 3, 10));
+
+    assert!(framer.fragments_queue.is_empty());
+    assert_eq!(drops(&mut framer), vec![
+        SplitDrop { split_id: 1, reason: SplitDropReason::Inconsistent },
+        SplitDrop { split_id: 2, reason: SplitDropReason::Inconsistent },
+    ]);
+}
+
+#[test]
+fn stalled_split_times_out() {
+    let mut framer = Framer::new(1400);
+    framer.handle_split_frame(&fragment(1, 0, 2, 10));
+
+    framer.tick(framer.split_limits.timeout - 1);
+    assert_eq!(framer.fragments_queue.len(), 1);
+
+    framer.tick(framer.split_limits.timeout);
+    assert!(framer.fragments_queue.is_empty());
+    assert_eq!(drops(&mut framer), vec![SplitDrop { split_id: 1, reason: SplitDropReason::TimedOut }]);
+}
+
+#[test]
+fn client_reports_dropped_splits_as_events() {
+    let mut client = Client::with_transport(SilentPeer::default(), ClientConfig::default()).unwrap();
+    let frameset = FrameSet::new(0, vec![fragment(7, 3, 2, 10)]);
+    client.handle_packet(&frameset.serialize()).unwrap();
+
+    let dropped: Vec<ClientEvent> = client.event_receiver.try_iter()
+        .filter(|event| matches!(event, ClientEvent::SplitDropped { .. }))
+        .collect();
+    assert_eq!(dropped, vec![ClientEvent::SplitDropped { split_id: 7, reason: SplitDropReason::Inconsistent }]);
+}
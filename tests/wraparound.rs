@@ -0,0 +1,136 @@
+use sanctumterra_raknet::*;
+
+const NEAR_WRAP: u32 = 0xfffff0;
+
+/// Starts both ends of a session a few steps short of the u24 wrap point.
+fn pair_near_wrap() -> (Framer, Framer) {
+    let mut sender = Framer::new(1400);
+    let mut receiver = Framer::new(1400);
+    sender.output_sequence = NEAR_WRAP;
+    sender.output_reliable_index = NEAR_WRAP;
+    sender.output_order_index[0] = NEAR_WRAP;
+    receiver.last_input_sequence = Some(NEAR_WRAP - 1);
+    receiver.input_reliable_window.base = NEAR_WRAP;
+    receiver.input_datagram_window.base = NEAR_WRAP;
+    receiver.input_order_index[0] = NEAR_WRAP;
+    (sender, receiver)
+}
+
+/// Runs both framers for `rounds` ticks, dropping the sender's datagrams for
+/// which `drop` returns true.
+fn exchange(sender: &mut Framer, receiver: &mut Framer, rounds: u64, drop: impl Fn(usize) -> bool) {
+    let mut sent = 0;
+    for round in 0..rounds {
+        let now = round * 50;
+        sender.tick(now);
+        while let Some(datagram) = sender.poll_datagram() {
+            if !drop(sent) {
+                receiver.receive(&datagram, now).unwrap();
+            }
+            sent += 1;
+        }
+        receiver.tick(now);
+        while let Some(datagram) = receiver.poll_datagram() {
+            sender.receive(&datagram, now).unwrap();
+        }
+    }
+}
+
+fn send(framer: &mut Framer, reliability: Reliability, payload: Vec<u8>) {
+    let mut frame = Frame::new();
+    frame.reliability = reliability;
+    frame.order_channel = Some(0);
+    frame.payload = payload;
+    framer.send_frame(&mut frame, Some(Priority::Immediate)).unwrap();
+}
+
+fn messages(framer: &mut Framer) -> Vec<Vec<u8>> {
+    std::iter::from_fn(|| framer.poll_message()).map(|frame| frame.payload).collect()
+}
+
+#[test]
+fn serial_comparisons_cross_the_wrap_point() {
+    assert_eq!(serial_next(U24_MASK), 0);
+    assert_eq!(serial_add(0xfffffe, 4), 2);
+    assert_eq!(serial_distance(0xfffffe, 2), 4);
+    assert!(serial_lt(0xffffff, 0));
+    assert!(serial_gt(0, 0xffffff));
+    assert!(!serial_lt(5, 5));
+    assert!(serial_lt(0, 0x7fffff));
+    assert!(!serial_lt(0, 0x800001));
+}
+
+#[test]
+fn reliable_window_drops_repeats_across_the_wrap_point() {
+    let mut window = ReliableWindow::new();
+    window.base = 0xfffffe;
+
+    assert!(window.receive(0));
+    assert!(window.receive(0xffffff));
+    assert!(!window.receive(0));
+    assert!(window.receive(0xfffffe));
+    assert_eq!(window.base, 1);
+    assert!(window.received.is_empty());
+    assert!(!window.receive(0xffffff));
+    assert!(window.receive(1));
+}
+
+#[test]
+fn ordered_messages_arrive_in_order_across_the_wrap_point() {
+    let (mut sender, mut receiver) = pair_near_wrap();
+    // Each payload needs its own datagram, so the sequence number wraps too.
+    let sent: Vec<Vec<u8>> = (0..64u8).map(|index| vec![index; 1000]).collect();
+    for payload in &sent {
+        send(&mut sender, Reliability::ReliableOrdered, payload.clone());
+    }
+
+    exchange(&mut sender, &mut receiver, 100, |index| index % 3 == 1);
+
+    assert_eq!(messages(&mut receiver), sent);
+    assert!(serial_lt(NEAR_WRAP, sender.output_sequence));
+    assert!(sender.output_sequence < NEAR_WRAP);
+    assert!(!sender.has_pending());
+}
+
+#[test]
+fn late_datagrams_across_the_wrap_point_are_not_delivered_twice() {
+    let (mut sender, mut receiver) = pair_near_wrap();
+    sender.congestion = Box::new(Unlimited);
+    for index in 0..32u8 {
+        send(&mut sender, Reliability::Reliable, vec![index]);
+    }
+    sender.tick(0);
+    let datagrams: Vec<Vec<u8>> = std::iter::from_fn(|| sender.poll_datagram()).collect();
+    for datagram in datagrams.iter().rev().chain(datagrams.iter()) {
+        receiver.receive(datagram, 0).unwrap();
+    }
+
+    let mut received = messages(&mut receiver);
+    received.sort();
+    assert_eq!(received, (0..32u8).map(|index| vec![index]).collect::<Vec<_>>());
+    assert!(receiver.lost_frame_sequences.is_empty());
+}
+
+#[test]
+fn sequenced_messages_drop_stale_ones_across_the_wrap_point() {
+    let mut receiver = Framer::new(1400);
+    receiver.input_order_index[0] = NEAR_WRAP;
+    receiver.input_highest_sequence_index[0] = 0xfffffe;
+
+    let sequenced = |sequence_frame_index: u32, payload: u8| {
+        let mut frame = Frame::new();
+        frame.reliability = Reliability::UnreliableSequenced;
+        frame.order_channel = Some(0);
+        frame.ordered_frame_index = Some(NEAR_WRAP);
+        frame.sequence_frame_index = Some(sequence_frame_index);
+        frame.payload = vec![payload];
+        frame
+    };
+    receiver.handle_frame(&sequenced(0xfffffe, 0));
+    receiver.handle_frame(&sequenced(1, 1));
+    receiver.handle_frame(&sequenced(0xffffff, 2));
+    receiver.handle_frame(&sequenced(0, 3));
+    receiver.handle_frame(&sequenced(2, 4));
+
+    assert_eq!(messages(&mut receiver), vec![vec![0], vec![1], vec![4]]);
+}